@@ -0,0 +1,310 @@
+use super::range_inc::*;
+use num_traits::ToPrimitive;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoxCondition<T: RangeNumber>
+{
+    LessThan(T),
+    GreaterThan(T),
+    #[allow(dead_code)]
+    Equal(T),
+}
+
+impl<T: RangeNumber> BoxCondition<T>
+{
+    fn range(&self) -> Option<RangeInc<T>>
+    {
+        // The values along an axis that match
+
+        match *self
+        {
+            BoxCondition::LessThan(v) if v == T::min_value() => None,
+            BoxCondition::LessThan(v) => Some(RangeInc::new_range(T::min_value(), v - T::one())),
+            BoxCondition::GreaterThan(v) if v == T::max_value() => None,
+            BoxCondition::GreaterThan(v) => Some(RangeInc::new_range(v + T::one(), T::max_value())),
+            BoxCondition::Equal(v) => Some(RangeInc::new_range(v, v)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HyperBox<T: RangeNumber, const N: usize>
+{
+    pub axes: [RangeInc<T>; N],
+}
+
+impl<T: RangeNumber, const N: usize> HyperBox<T, N>
+{
+    pub fn new(axes: [RangeInc<T>; N]) -> Self
+    {
+        HyperBox { axes }
+    }
+
+    pub fn all() -> Self
+    {
+        HyperBox { axes: [RangeInc::all(); N] }
+    }
+
+    pub fn contains_point(&self, point: [T; N]) -> bool
+    {
+        self.axes.iter().zip(point.iter()).all(|(r, p)| r.contains(*p))
+    }
+
+    pub fn intersection(&self, other: &HyperBox<T, N>) -> Option<HyperBox<T, N>>
+    {
+        let mut result = *self;
+
+        for (axis, r) in result.axes.iter_mut().enumerate()
+        {
+            *r = r.intersection(other.axes[axis])?;
+        }
+
+        Some(result)
+    }
+
+    pub fn difference(&self, other: &HyperBox<T, N>) -> Vec<HyperBox<T, N>>
+    {
+        // Peels off the parts outside the other box one
+        // axis at a time, shrinking what's left to the
+        // overlap on each axis as it goes
+
+        let overlap = match self.intersection(other)
+        {
+            Some(o) => o,
+            None => return vec![*self],
+        };
+
+        let mut result = Vec::new();
+        let mut remaining = *self;
+
+        for axis in 0..N
+        {
+            for piece in remaining.axes[axis].difference(overlap.axes[axis])
+            {
+                let mut b = remaining;
+                b.axes[axis] = piece;
+                result.push(b);
+            }
+
+            remaining.axes[axis] = overlap.axes[axis];
+        }
+
+        result
+    }
+}
+
+impl<T: RangeNumber + ToPrimitive, const N: usize> HyperBox<T, N>
+{
+    pub fn count(&self) -> Option<u128>
+    {
+        // None if there are too many points to fit in
+        // a u128 - e.g. more than one unbounded i64 axis
+
+        self.axes.iter()
+            .map(|r| (r.end.to_i128().unwrap() - r.start.to_i128().unwrap()) as u128 + 1)
+            .try_fold(1u128, |total, len| total.checked_mul(len))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BoxSet<T: RangeNumber, const N: usize>
+{
+    // The boxes are always kept disjoint, so
+    // the count is the sum of their counts
+
+    boxes: Vec<HyperBox<T, N>>,
+}
+
+impl<T: RangeNumber, const N: usize> BoxSet<T, N>
+{
+    pub fn new() -> Self
+    {
+        BoxSet { boxes: Vec::new() }
+    }
+
+    pub fn new_from_box(b: HyperBox<T, N>) -> Self
+    {
+        BoxSet { boxes: vec![b] }
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.boxes.is_empty()
+    }
+
+    #[allow(dead_code)]
+    pub fn boxes(&self) -> impl Iterator<Item = HyperBox<T, N>> + '_
+    {
+        self.boxes.iter().copied()
+    }
+
+    #[allow(dead_code)]
+    pub fn contains_point(&self, point: [T; N]) -> bool
+    {
+        self.boxes.iter().any(|b| b.contains_point(point))
+    }
+
+    pub fn insert_box(&mut self, b: HyperBox<T, N>)
+    {
+        self.remove_box(b);
+        self.boxes.push(b);
+    }
+
+    pub fn remove_box(&mut self, b: HyperBox<T, N>)
+    {
+        self.boxes = self.boxes.iter()
+            .flat_map(|existing| existing.difference(&b))
+            .collect();
+    }
+
+    pub fn union(&self, other: &BoxSet<T, N>) -> BoxSet<T, N>
+    {
+        let mut result = self.clone();
+
+        for b in other.boxes.iter()
+        {
+            result.insert_box(*b);
+        }
+
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn difference(&self, other: &BoxSet<T, N>) -> BoxSet<T, N>
+    {
+        let mut result = self.clone();
+
+        for b in other.boxes.iter()
+        {
+            result.remove_box(*b);
+        }
+
+        result
+    }
+
+    pub fn intersection_with_box(&self, b: &HyperBox<T, N>) -> BoxSet<T, N>
+    {
+        BoxSet { boxes: self.boxes.iter().filter_map(|existing| existing.intersection(b)).collect() }
+    }
+
+    #[allow(dead_code)]
+    pub fn intersection(&self, other: &BoxSet<T, N>) -> BoxSet<T, N>
+    {
+        // Both sets are disjoint, so the pairwise
+        // intersections are also disjoint
+
+        BoxSet
+        {
+            boxes: self.boxes.iter()
+                .flat_map(|a| other.boxes.iter().filter_map(move |b| a.intersection(b)))
+                .collect()
+        }
+    }
+
+    pub fn split(&self, axis: usize, condition: BoxCondition<T>) -> (BoxSet<T, N>, BoxSet<T, N>)
+    {
+        // Returns the points where the condition on
+        // the axis holds, and the points where it doesn't
+
+        let range = match condition.range()
+        {
+            Some(r) => r,
+            None => return (BoxSet::new(), self.clone()),
+        };
+
+        let mut matching = HyperBox::all();
+        matching.axes[axis] = range;
+
+        let mut rest = self.clone();
+        rest.remove_box(matching);
+
+        (self.intersection_with_box(&matching), rest)
+    }
+}
+
+impl<T: RangeNumber + ToPrimitive, const N: usize> BoxSet<T, N>
+{
+    pub fn count(&self) -> Option<u128>
+    {
+        self.boxes.iter().try_fold(0u128, |total, b| total.checked_add(b.count()?))
+    }
+}
+
+impl<T: RangeNumber, const N: usize> Default for BoxSet<T, N>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn cube(min: i64, max: i64) -> HyperBox<i64, 3>
+    {
+        HyperBox::new([RangeInc::new_range(min, max); 3])
+    }
+
+    #[test]
+    fn test_box_set()
+    {
+        let mut set = BoxSet::new_from_box(cube(0, 9));
+        set.remove_box(cube(3, 5));
+
+        assert_eq!(set.count(), Some(1000 - 27));
+        assert!(!set.contains_point([4, 4, 4]));
+        assert!(set.contains_point([4, 4, 6]));
+
+        let both = set.union(&BoxSet::new_from_box(cube(5, 12)));
+        assert_eq!(both.count(), Some(1000 - 27 + 512 - 125 + 1));
+
+        let back = both.difference(&BoxSet::new_from_box(cube(5, 12)));
+        assert_eq!(back.count(), Some(1000 - 27 - (125 - 1)));
+        assert_eq!(set.intersection(&back).count(), back.count());
+
+        // Brute force count of every point
+
+        let points = (-1..14)
+            .flat_map(|x| (-1..14).flat_map(move |y| (-1..14).map(move |z| [x, y, z])))
+            .filter(|p| both.contains_point(*p))
+            .count();
+
+        assert_eq!(Some(points as u128), both.count());
+    }
+
+    #[test]
+    fn test_box_set_split()
+    {
+        let set = BoxSet::new_from_box(HyperBox::new([RangeInc::new_range(1, 4000); 4]));
+
+        let (low, high) = set.split(1, BoxCondition::LessThan(1001));
+        assert_eq!(low.count(), Some(1000 * 4000u128.pow(3)));
+        assert_eq!(high.count(), Some(3000 * 4000u128.pow(3)));
+
+        let (eq, rest) = high.split(3, BoxCondition::Equal(7));
+        assert_eq!(eq.count(), Some(3000 * 4000u128.pow(2)));
+        assert_eq!(rest.count(), Some(3000 * 3999 * 4000u128.pow(2)));
+
+        let (none, all) = set.split(0, BoxCondition::GreaterThan(4000));
+        assert!(none.is_empty());
+        assert_eq!(all.count(), Some(4000u128.pow(4)));
+
+        // Unbounded axes
+
+        let everything = BoxSet::<i64, 1>::new_from_box(HyperBox::all());
+        assert_eq!(everything.count(), Some(1 << 64));
+
+        let strip = BoxSet::new_from_box(HyperBox::new([RangeInc::all(), RangeInc::new_range(0i64, 9)]));
+        let (negative, rest) = strip.split(0, BoxCondition::LessThan(0));
+        assert_eq!(strip.count(), Some(10 << 64));
+        assert_eq!(negative.count(), Some(10 << 63));
+        assert_eq!(rest.count(), Some(10 << 63));
+
+        let plane = BoxSet::<i64, 2>::new_from_box(HyperBox::all());
+        assert_eq!(plane.count(), None);
+        assert_eq!(plane.split(1, BoxCondition::Equal(0)).0.count(), Some(1 << 64));
+    }
+}
@@ -1,7 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::fmt::Debug;
 
+#[allow(dead_code)]
 pub fn bron_kerbosch_maximal_cliques<'a, V, SIV, FN, FNIV>(
     verticies: SIV,
     neighbours: FN) -> Vec<HashSet<V>>
@@ -10,48 +11,342 @@ pub fn bron_kerbosch_maximal_cliques<'a, V, SIV, FN, FNIV>(
         FNIV: Iterator<Item = V>,
         V: 'a + Clone + Hash + PartialEq + Eq + Debug
 {
-    let p = verticies.cloned().collect::<HashSet<V>>();
-    let mut r = HashSet::new();
-    let x = HashSet::new();
-    let mut results = Vec::new();
+    CliqueGraph::new(verticies, neighbours)
+        .maximal_cliques()
+        .into_iter()
+        .map(|c| c.into_iter().collect())
+        .collect()
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct VertexSet
+{
+    // One bit per vertex index
+
+    words: Vec<u64>,
+}
+
+impl VertexSet
+{
+    fn new(size: usize) -> Self
+    {
+        VertexSet { words: vec![0; size.div_ceil(64)] }
+    }
+
+    fn insert(&mut self, v: usize)
+    {
+        self.words[v / 64] |= 1 << (v % 64);
+    }
+
+    fn remove(&mut self, v: usize)
+    {
+        self.words[v / 64] &= !(1 << (v % 64));
+    }
+
+    fn is_empty(&self) -> bool
+    {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    fn len(&self) -> usize
+    {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn intersection(&self, other: &VertexSet) -> VertexSet
+    {
+        VertexSet { words: self.words.iter().zip(other.words.iter()).map(|(a, b)| a & b).collect() }
+    }
+
+    fn intersection_len(&self, other: &VertexSet) -> usize
+    {
+        self.words.iter().zip(other.words.iter()).map(|(a, b)| (a & b).count_ones() as usize).sum()
+    }
+
+    fn difference(&self, other: &VertexSet) -> VertexSet
+    {
+        VertexSet { words: self.words.iter().zip(other.words.iter()).map(|(a, b)| a & !b).collect() }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_
+    {
+        self.words.iter()
+            .enumerate()
+            .flat_map(|(i, w)| (0..64).filter(move |b| (w & (1 << b)) != 0).map(move |b| i * 64 + b))
+    }
+}
+
+pub struct CliqueGraph<V>
+    where V: Clone + Hash + Eq,
+{
+    // Vertices are numbered in the order they
+    // were given, and cliques are returned with
+    // their vertices in the same order
+
+    verticies: Vec<V>,
+    neighbours: Vec<VertexSet>,
+}
+
+impl<V> CliqueGraph<V>
+    where V: Clone + Hash + Eq,
+{
+    pub fn new<'a, SIV, FN, FNIV>(verticies: SIV, neighbours: FN) -> Self
+        where FN: Fn(&V) -> FNIV,
+            SIV: Iterator<Item = &'a V>,
+            FNIV: Iterator<Item = V>,
+            V: 'a,
+    {
+        let verticies = verticies.cloned().collect::<Vec<_>>();
+        let indices = verticies.iter().enumerate().map(|(i, v)| (v.clone(), i)).collect::<HashMap<_, _>>();
+
+        let neighbours = verticies.iter()
+            .map(|v|
+            {
+                let mut set = VertexSet::new(verticies.len());
+
+                for n in neighbours(v)
+                {
+                    if let Some(i) = indices.get(&n)
+                    {
+                        set.insert(*i);
+                    }
+                }
+
+                set
+            })
+            .collect();
+
+        CliqueGraph { verticies, neighbours }
+    }
+
+    fn all(&self) -> VertexSet
+    {
+        let mut all = VertexSet::new(self.verticies.len());
+
+        for v in 0..self.verticies.len()
+        {
+            all.insert(v);
+        }
+
+        all
+    }
+
+    fn to_verticies(&self, set: &VertexSet) -> Vec<V>
+    {
+        set.iter().map(|i| self.verticies[i].clone()).collect()
+    }
+
+    fn degeneracy_order(&self) -> Vec<usize>
+    {
+        // Repeatedly remove the vertex with the fewest
+        // remaining neighbours. Each vertex then has few
+        // neighbours later in the order.
+
+        let mut remaining = self.all();
+        let mut order = Vec::new();
+
+        while let Some(v) = remaining.iter().min_by_key(|v| self.neighbours[*v].intersection_len(&remaining))
+        {
+            order.push(v);
+            remaining.remove(v);
+        }
+
+        order
+    }
+
+    fn choose_pivot(&self, p: &VertexSet, x: &VertexSet) -> usize
+    {
+        // Tomita - the pivot with the most neighbours
+        // in P leaves the fewest branches to try
+
+        p.iter().chain(x.iter())
+            .max_by_key(|u| self.neighbours[*u].intersection_len(p))
+            .unwrap()
+    }
+
+    fn expand(&self, r: &mut VertexSet, mut p: VertexSet, mut x: VertexSet, visit: &mut dyn FnMut(&VertexSet, &VertexSet, &VertexSet) -> bool)
+    {
+        // Calls visit with R, P and X - it can
+        // return false to skip expanding this branch
+
+        if !visit(r, &p, &x) || p.is_empty()
+        {
+            return;
+        }
+
+        let pivot = self.choose_pivot(&p, &x);
+
+        for v in p.difference(&self.neighbours[pivot]).iter().collect::<Vec<_>>()
+        {
+            r.insert(v);
+            self.expand(r, p.intersection(&self.neighbours[v]), x.intersection(&self.neighbours[v]), visit);
+            r.remove(v);
+
+            p.remove(v);
+            x.insert(v);
+        }
+    }
+
+    fn expand_in_degeneracy_order(&self, visit: &mut dyn FnMut(&VertexSet, &VertexSet, &VertexSet) -> bool)
+    {
+        // The top level goes through the vertices in
+        // degeneracy order, so that each P starts small
+
+        let mut p = self.all();
+        let mut x = VertexSet::new(self.verticies.len());
+        let mut r = VertexSet::new(self.verticies.len());
+
+        for v in self.degeneracy_order()
+        {
+            r.insert(v);
+            self.expand(&mut r, p.intersection(&self.neighbours[v]), x.intersection(&self.neighbours[v]), visit);
+            r.remove(v);
+
+            p.remove(v);
+            x.insert(v);
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn maximal_cliques(&self) -> Vec<Vec<V>>
+    {
+        let mut results = Vec::new();
+
+        self.expand_in_degeneracy_order(&mut |r, p, x|
+        {
+            if p.is_empty() && x.is_empty()
+            {
+                results.push(self.to_verticies(r));
+            }
+            true
+        });
+
+        results
+    }
+
+    pub fn maximum_clique(&self) -> Vec<V>
+    {
+        // Branch and bound - give up on any branch
+        // which can't beat the best found so far
 
-    apply(&mut r, p, x, &neighbours, &mut results);
+        let mut best = VertexSet::new(self.verticies.len());
 
-    results
+        self.expand_in_degeneracy_order(&mut |r, p, _|
+        {
+            if r.len() > best.len()
+            {
+                best = r.clone();
+            }
+            r.len() + p.len() > best.len()
+        });
+
+        self.to_verticies(&best)
+    }
+
+    #[allow(dead_code)]
+    pub fn k_cliques(&self, k: usize) -> Vec<Vec<V>>
+    {
+        self.k_cliques_filtered(k, |_| true)
+    }
+
+    pub fn k_cliques_filtered<F>(&self, k: usize, filter: F) -> Vec<Vec<V>>
+        where F: Fn(&[V]) -> bool,
+    {
+        // Every clique of exactly k vertices - not only
+        // the maximal ones. Each is built up in index
+        // order, so is only found once.
+
+        fn extend<V, F>(graph: &CliqueGraph<V>, k: usize, clique: &mut Vec<usize>, candidates: VertexSet, filter: &F, results: &mut Vec<Vec<V>>)
+            where V: Clone + Hash + Eq,
+                F: Fn(&[V]) -> bool,
+        {
+            if clique.len() == k
+            {
+                let verticies = clique.iter().map(|i| graph.verticies[*i].clone()).collect::<Vec<_>>();
+
+                if filter(&verticies)
+                {
+                    results.push(verticies);
+                }
+                return;
+            }
+
+            for v in candidates.iter().collect::<Vec<_>>()
+            {
+                let mut later = candidates.intersection(&graph.neighbours[v]);
+
+                for earlier in 0..=v
+                {
+                    later.remove(earlier);
+                }
+
+                if clique.len() + 1 + later.len() >= k
+                {
+                    clique.push(v);
+                    extend(graph, k, clique, later, filter, results);
+                    clique.pop();
+                }
+            }
+        }
+
+        let mut results = Vec::new();
+
+        extend(self, k, &mut Vec::new(), self.all(), &filter, &mut results);
+
+        results
+    }
 }
 
-fn apply<'a, V, FN, FNIV>(r: &'a mut HashSet<V>, mut p: HashSet<V>, mut x: HashSet<V>, neighbours: &'a FN, results: &'a mut Vec<HashSet<V>>)
-    where FN: 'a + Fn(&V) -> FNIV,
-    FNIV: Iterator<Item = V>,
-    V: 'a + Clone + Hash + PartialEq + Eq + Debug
+#[cfg(test)]
+mod tests
 {
-    if p.is_empty() && x.is_empty()
+    use super::*;
+
+    fn example_graph() -> CliqueGraph<usize>
     {
-        results.push(r.clone());
-        return;
+        // Two 4-cliques sharing vertex 3, plus
+        // a triangle and an isolated vertex
+
+        let edges = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3),
+                     (3, 4), (3, 5), (3, 6), (4, 5), (4, 6), (5, 6),
+                     (7, 8), (8, 9), (7, 9), (6, 7)];
+
+        let verticies = (0..11).collect::<Vec<usize>>();
+
+        CliqueGraph::new(
+            verticies.iter(),
+            |v| edges.iter()
+                .filter_map(|(a, b)| if a == v { Some(*b) } else if b == v { Some(*a) } else { None })
+                .collect::<Vec<_>>()
+                .into_iter())
     }
 
-    while let Some(v) = p.iter().cloned().next()
+    #[test]
+    fn test_maximal_cliques()
     {
-        // apply(R u {v}, P ^ N(v), X ^ N(v))
+        let mut cliques = example_graph().maximal_cliques();
+
+        for c in cliques.iter_mut()
         {
-            r.insert(v.clone());
-            let n = neighbours(&v).collect::<HashSet<V>>();
-            let sub_p = p.iter()
-                .filter(|sp| n.contains(sp))
-                .cloned()
-                .collect();
-            let sub_x  = x.iter()
-                .filter(|sv| n.contains(sv))
-                .cloned()
-                .collect();
-            apply(r, sub_p, sub_x, neighbours, results);
-            r.remove(&v);
+            c.sort_unstable();
         }
+        cliques.sort();
 
-        // P := P \ {v}
-        p.remove(&v);
-        // X := X u {v}
-        x.insert(v);
+        assert_eq!(cliques, vec![vec![0, 1, 2, 3], vec![3, 4, 5, 6], vec![6, 7], vec![7, 8, 9], vec![10]]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_maximum_and_k_cliques()
+    {
+        let graph = example_graph();
+
+        assert_eq!(graph.maximum_clique().len(), 4);
+
+        // 4 from each 4-clique, plus the other triangle
+
+        assert_eq!(graph.k_cliques(3).len(), 9);
+        assert_eq!(graph.k_cliques(4).len(), 2);
+        assert_eq!(graph.k_cliques(5).len(), 0);
+        assert_eq!(graph.k_cliques_filtered(3, |c| c.contains(&3)).len(), 6);
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Finding repeating states in simulations. The step
+// function advances a state in place, and the key
+// projects out the part of the state that has to
+// repeat - e.g. everything except a growing counter.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle
+{
+    // The state after start + length steps
+    // matches the state after start steps
+
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle
+{
+    pub fn reduce_steps(&self, n: u64) -> usize
+    {
+        // An equivalent number of steps that is
+        // no more than start + length
+
+        if n < (self.start + self.length) as u64
+        {
+            n as usize
+        }
+        else
+        {
+            self.start + ((n - self.start as u64) % self.length as u64) as usize
+        }
+    }
+}
+
+fn record_until_cycle<S, K, FS, FK, R, FR>(initial: S, mut step: FS, key: FK, mut record: FR, limit: u64) -> (Option<Cycle>, Vec<R>)
+    where
+    FS: FnMut(&mut S),
+    FK: Fn(&S) -> K,
+    K: Hash + Eq,
+    FR: FnMut(&S) -> R,
+{
+    // Records each state until either a cycle is
+    // found, or the limit on steps is reached
+
+    let mut state = initial;
+    let mut seen = HashMap::new();
+    let mut records = Vec::new();
+
+    loop
+    {
+        records.push(record(&state));
+
+        let index = records.len() - 1;
+
+        if let Some(prev) = seen.insert(key(&state), index)
+        {
+            return (Some(Cycle { start: prev, length: index - prev }), records);
+        }
+
+        if index as u64 >= limit
+        {
+            return (None, records);
+        }
+
+        step(&mut state);
+    }
+}
+
+#[allow(dead_code)]
+pub fn find_cycle<S, K, FS, FK>(initial: &S, step: FS, key: FK) -> Cycle
+    where
+    S: Clone,
+    FS: FnMut(&mut S),
+    FK: Fn(&S) -> K,
+    K: Hash + Eq,
+{
+    // Remembers every key - fast, but
+    // uses memory for the whole history
+
+    record_until_cycle(initial.clone(), step, key, |_| (), u64::MAX).0.unwrap()
+}
+
+pub fn find_cycle_brent<S, K, FS, FK>(initial: &S, mut step: FS, key: FK) -> Cycle
+    where
+    S: Clone,
+    FS: FnMut(&mut S),
+    FK: Fn(&S) -> K,
+    K: Eq,
+{
+    // Only keeps two states - the hare runs ahead
+    // while the tortoise teleports to it at each
+    // power of two
+
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    step(&mut hare);
+
+    while key(&tortoise) != key(&hare)
+    {
+        if power == length
+        {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        step(&mut hare);
+        length += 1;
+    }
+
+    // Start the hare one cycle ahead, and then
+    // they meet at the start of the cycle
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+
+    for _ in 0..length
+    {
+        step(&mut hare);
+    }
+
+    let mut start = 0;
+
+    while key(&tortoise) != key(&hare)
+    {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+#[allow(dead_code)]
+pub fn find_cycle_floyd<S, K, FS, FK>(initial: &S, mut step: FS, key: FK) -> Cycle
+    where
+    S: Clone,
+    FS: FnMut(&mut S),
+    FK: Fn(&S) -> K,
+    K: Eq,
+{
+    // The hare moves twice as fast as the tortoise
+    // until they meet somewhere inside the cycle
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    step(&mut tortoise);
+    step(&mut hare);
+    step(&mut hare);
+
+    while key(&tortoise) != key(&hare)
+    {
+        step(&mut tortoise);
+        step(&mut hare);
+        step(&mut hare);
+    }
+
+    let mut tortoise = initial.clone();
+    let mut start = 0;
+
+    while key(&tortoise) != key(&hare)
+    {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+
+    let mut hare = tortoise.clone();
+    step(&mut hare);
+    let mut length = 1;
+
+    while key(&tortoise) != key(&hare)
+    {
+        step(&mut hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+pub fn state_after_steps<S, K, FS, FK>(initial: S, step: FS, key: FK, n: u64) -> S
+    where
+    S: Clone,
+    FS: FnMut(&mut S),
+    FK: Fn(&S) -> K,
+    K: Hash + Eq,
+{
+    let (cycle, mut states) = record_until_cycle(initial, step, key, |s| s.clone(), n);
+
+    let index = match cycle
+    {
+        Some(c) => c.reduce_steps(n),
+        None => n as usize,
+    };
+
+    states.swap_remove(index)
+}
+
+pub fn metric_after_steps<S, K, FS, FK, FM>(initial: S, step: FS, key: FK, metric: FM, n: u64) -> i64
+    where
+    FS: FnMut(&mut S),
+    FK: Fn(&S) -> K,
+    K: Hash + Eq,
+    FM: Fn(&S) -> i64,
+{
+    // For a metric that grows by the same amount each
+    // time around the cycle, such as the height of
+    // a tower - which mustn't be part of the key
+
+    let (cycle, metrics) = record_until_cycle(initial, step, key, metric, n);
+
+    match cycle
+    {
+        None => metrics[n as usize],
+        Some(c) =>
+        {
+            if n < (c.start + c.length) as u64
+            {
+                return metrics[n as usize];
+            }
+
+            let per_cycle = metrics[c.start + c.length] - metrics[c.start];
+            let num_cycles = (n - c.start as u64) / (c.length as u64);
+
+            metrics[c.reduce_steps(n)] + (num_cycles as i64) * per_cycle
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    // x -> x^2 + 1 mod 255, starting at 3, goes
+    // 3, 10, 101, 2, 5, 26, 167, 95, 101, ...
+
+    fn step(x: &mut u64)
+    {
+        *x = (*x * *x + 1) % 255;
+    }
+
+    #[test]
+    fn test_find_cycle()
+    {
+        let expected = Cycle { start: 2, length: 6 };
+
+        assert_eq!(find_cycle(&3, step, |x| *x), expected);
+        assert_eq!(find_cycle_brent(&3, step, |x| *x), expected);
+        assert_eq!(find_cycle_floyd(&3, step, |x| *x), expected);
+
+        assert_eq!(find_cycle_brent(&0, |x: &mut u64| *x = (*x + 1) % 5, |x| *x), Cycle { start: 0, length: 5 });
+    }
+
+    #[test]
+    fn test_steps_through_cycle()
+    {
+        let brute_force = |n: u64|
+        {
+            let mut x = 3;
+            for _ in 0..n { step(&mut x); }
+            x
+        };
+
+        for n in [0, 1, 5, 9, 10, 100, 1001]
+        {
+            assert_eq!(state_after_steps(3, step, |x| *x, n), brute_force(n));
+        }
+
+        // A counter that goes up 3 for each time
+        // around a cycle of length 4
+
+        let counter_step = |s: &mut (i64, i64)|
+        {
+            s.0 += 1;
+            if s.0 == 6 { s.0 = 2; s.1 += 3; }
+        };
+
+        for n in [0, 3, 4, 5, 6, 7, 1000]
+        {
+            let mut s = (0, 0);
+            for _ in 0..n { counter_step(&mut s); }
+
+            assert_eq!(metric_after_steps((0, 0), counter_step, |s| s.0, |s| s.1 * 10 + s.0, n), s.1 * 10 + s.0);
+        }
+    }
+}
@@ -1,9 +1,17 @@
+pub mod box_set;
 pub mod clique;
+pub mod cycle;
 pub mod range_inc;
+pub mod range_map;
 pub mod range_set;
 pub mod search;
+pub mod union_find;
 
+pub use box_set::*;
 pub use clique::*;
+pub use cycle::*;
 pub use range_inc::*;
+pub use range_map::*;
 pub use range_set::*;
-pub use search::*;
\ No newline at end of file
+pub use search::*;
+pub use union_find::*;
\ No newline at end of file
@@ -0,0 +1,252 @@
+use super::range_inc::*;
+use super::range_set::*;
+use std::fmt::Debug;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct RangeMap<T: RangeNumber, V: Clone + PartialEq>
+{
+    // Disjoint ranges in increasing order, each with a
+    // value. Neighbouring ranges with the same value
+    // are joined together.
+
+    entries: Vec<(RangeInc<T>, V)>,
+}
+
+impl<T: RangeNumber, V: Clone + PartialEq> RangeMap<T, V>
+{
+    pub fn new() -> Self
+    {
+        RangeMap { entries: Vec::new() }
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool
+    {
+        self.entries.is_empty()
+    }
+
+    #[allow(dead_code)]
+    pub fn entries(&self) -> impl Iterator<Item = &(RangeInc<T>, V)>
+    {
+        self.entries.iter()
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, point: T) -> Option<&V>
+    {
+        let index = self.entries.partition_point(|(r, _)| r.end < point);
+
+        self.entries.get(index)
+            .filter(|(r, _)| r.contains(point))
+            .map(|(_, v)| v)
+    }
+
+    pub fn insert(&mut self, range: RangeInc<T>, value: V)
+    {
+        // Overrides any existing values in the range
+
+        let mut entries = Vec::with_capacity(self.entries.len() + 2);
+
+        for (r, v) in self.entries.drain(..)
+        {
+            for remaining in r.difference(range)
+            {
+                entries.push((remaining, v.clone()));
+            }
+        }
+
+        entries.push((range, value));
+        entries.sort_by_key(|(r, _)| r.start);
+
+        for (r, v) in entries
+        {
+            match self.entries.last_mut()
+            {
+                Some((last, last_v)) if (*last_v == v) && (last.end + T::one() == r.start) =>
+                {
+                    last.end = r.end;
+                },
+                _ => self.entries.push((r, v)),
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn overlay(&mut self, other: &RangeMap<T, V>)
+    {
+        // The other map's values win wherever
+        // both maps have a value
+
+        for (r, v) in other.entries.iter()
+        {
+            self.insert(*r, v.clone());
+        }
+    }
+
+    pub fn pieces(&self, range: RangeInc<T>) -> Vec<(RangeInc<T>, Option<&V>)>
+    {
+        // Splits the range up into the parts with
+        // each value, and the parts without one
+
+        let mut result = Vec::new();
+        let mut next_start = Some(range.start);
+
+        for (r, v) in self.entries.iter()
+        {
+            let start = match next_start
+            {
+                Some(s) => s,
+                None => break,
+            };
+
+            if r.end < start
+            {
+                continue;
+            }
+            if r.start > range.end
+            {
+                break;
+            }
+
+            if r.start > start
+            {
+                result.push((RangeInc::new_range(start, r.start - T::one()), None));
+            }
+
+            let overlap = r.intersection(range).unwrap();
+            result.push((overlap, Some(v)));
+
+            next_start = if overlap.end < range.end { Some(overlap.end + T::one()) } else { None };
+        }
+
+        if let Some(start) = next_start
+        {
+            result.push((RangeInc::new_range(start, range.end), None));
+        }
+
+        result
+    }
+}
+
+impl<T: RangeNumber> RangeMap<T, T>
+{
+    // Maps where each value is an offset to add on.
+    // Points without an offset map to themselves.
+
+    #[allow(dead_code)]
+    pub fn map_value(&self, point: T) -> T
+    {
+        point + self.get(point).copied().unwrap_or_else(T::zero)
+    }
+
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T>
+    {
+        let mut result = RangeSet::new();
+
+        for range in set.ranges()
+        {
+            for (piece, offset) in self.pieces(range)
+            {
+                let offset = offset.copied().unwrap_or_else(T::zero);
+
+                result.insert_range(RangeInc::new_range(piece.start + offset, piece.end + offset));
+            }
+        }
+
+        result
+    }
+
+    pub fn compose(&self, then: &RangeMap<T, T>) -> RangeMap<T, T>
+    {
+        // A single map that does this map, then the
+        // other one. Each piece of this map is moved
+        // by its offset, and then split up by the
+        // other map's pieces.
+
+        let mut result = RangeMap::new();
+
+        for (piece, offset) in self.pieces(RangeInc::all())
+        {
+            let offset = offset.copied().unwrap_or_else(T::zero);
+            let moved = RangeInc::new_range(piece.start + offset, piece.end + offset);
+
+            for (sub, then_offset) in then.pieces(moved)
+            {
+                let total = offset + then_offset.copied().unwrap_or_else(T::zero);
+
+                if total != T::zero()
+                {
+                    result.insert(RangeInc::new_range(sub.start - offset, sub.end - offset), total);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl<T: RangeNumber, V: Clone + PartialEq + Debug> Debug for RangeMap<T, V>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_map().entries(self.entries.iter().map(|(r, v)| (r, v))).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_range_map_insert()
+    {
+        let mut map = RangeMap::new();
+
+        map.insert(RangeInc::new_range(0, 9), 'a');
+        map.insert(RangeInc::new_range(20, 29), 'b');
+        map.insert(RangeInc::new_range(5, 24), 'c');
+        map.insert(RangeInc::new_range(25, 27), 'c');
+
+        assert_eq!(format!("{:?}", map), "{0..=4: 'a', 5..=27: 'c', 28..=29: 'b'}");
+        assert_eq!(map.get(4), Some(&'a'));
+        assert_eq!(map.get(28), Some(&'b'));
+        assert_eq!(map.get(30), None);
+
+        let mut under = RangeMap::new();
+        under.insert(RangeInc::new_range(-10, 40), 'z');
+        under.overlay(&map);
+
+        assert_eq!(format!("{:?}", under), "{-10..=-1: 'z', 0..=4: 'a', 5..=27: 'c', 28..=29: 'b', 30..=40: 'z'}");
+
+        let pieces = map.pieces(RangeInc::new_range(-5, 7));
+        assert_eq!(pieces, vec![(RangeInc::new_range(-5, -1), None), (RangeInc::new_range(0, 4), Some(&'a')), (RangeInc::new_range(5, 7), Some(&'c'))]);
+    }
+
+    #[test]
+    fn test_range_map_offsets()
+    {
+        // 10..=19 moves up by 100, and
+        // 50..=59 moves down to 10..=19
+
+        let mut first = RangeMap::new();
+        first.insert(RangeInc::new_range(10, 19), 100);
+        first.insert(RangeInc::new_range(50, 59), -40);
+
+        let mut second = RangeMap::new();
+        second.insert(RangeInc::new_range(15, 114), 1000);
+
+        let both = first.compose(&second);
+
+        for x in -5..200
+        {
+            assert_eq!(both.map_value(x), second.map_value(first.map_value(x)), "x = {}", x);
+        }
+
+        let mut set = RangeSet::new();
+        set.insert_range(RangeInc::new_range(0, 12));
+        set.insert_range(RangeInc::new_range(55, 55));
+
+        assert_eq!(format!("{:?}", first.map_set(&set)), "[0..=9, 15..=15, 110..=112]");
+    }
+}
@@ -82,6 +82,7 @@ impl<T: RangeNumber> RangeSet<T> {
         }
     }
 
+    #[allow(unused)]
     pub fn insersection_with_range(mut self, range: RangeInc<T>) -> Self {
         self.insersect_with_range(range);
         self
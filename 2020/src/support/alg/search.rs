@@ -1,6 +1,7 @@
 pub use std::cmp::Ordering;
-use std::collections::VecDeque;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
 use std::fmt::Debug;
+use std::hash::Hash;
 
 #[allow(unused)]
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -12,144 +13,295 @@ pub enum SearchDebugLevel
     Everything,
 }
 
-pub fn search<S, FN, I, FS, FC, FR>(
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchStats
+{
+    pub expanded: u64,
+    pub generated: u64,
+    pub duplicates: u64,
+    pub abandoned: u64,
+    pub rejected_solutions: u64,
+}
+
+pub struct SearchResult<S>
+{
+    pub best: Option<S>,
+    #[allow(dead_code)]
+    pub stats: SearchStats,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Frontier
+{
+    Queue,
+    Stack,
+    Heap,
+}
+
+type StateFn<'a, S, R> = Box<dyn Fn(&S) -> R + 'a>;
+type StatePairFn<'a, S, R> = Box<dyn Fn(&S, &S) -> R + 'a>;
+type SeenFn<'a, S> = Box<dyn FnMut(&S) -> bool + 'a>;
+
+pub struct Search<'a, S>
+{
+    // States are compared with compare_successes, where
+    // Ordering::Less is a better solution. Once there is
+    // a solution, only states passing the prune check
+    // are expanded.
+
     initial: S,
-    neighbours: FN,
-    is_success: FS,
-    compare_successes: FC,
-    can_state_reach_better_than_solution: FR,
-    debug: SearchDebugLevel) -> Option<S>
-    where
-    S: Debug,
-    FN: Fn(&S) -> I,
-    I: IntoIterator<Item = S>,
-    FS: Fn(&S) -> bool,
-    FC: Fn(&S, &S) -> Ordering,
-    FR: Fn(&S, &S) -> bool,
+    neighbours: StateFn<'a, S, Vec<S>>,
+    is_success: StateFn<'a, S, bool>,
+    compare_successes: StatePairFn<'a, S, Ordering>,
+    can_reach_better: StatePairFn<'a, S, bool>,
+    frontier: Frontier,
+    bound: Option<StateFn<'a, S, i64>>,
+    is_new: Option<SeenFn<'a, S>>,
+    debug: SearchDebugLevel,
+}
+
+impl<'a, S> Search<'a, S>
+    where S: Debug + 'a
 {
-    let mut best_solution = None;
-    let mut generation = 0;
-    let mut total_generated = 0u64;
-    let mut rejected_solutions = 0u64;
-    let mut total_abandoned = 0u64;
-
-    let mut to_search = VecDeque::new();
-    to_search.push_back(StateCompare{
-        cmp: &compare_successes,
-        generation: generation,
-        state: initial
-    });
-
-    while !to_search.is_empty()
+    pub fn new<FN, I, FS, FC>(initial: S, neighbours: FN, is_success: FS, compare_successes: FC) -> Self
+        where
+        FN: Fn(&S) -> I + 'a,
+        I: IntoIterator<Item = S>,
+        FS: Fn(&S) -> bool + 'a,
+        FC: Fn(&S, &S) -> Ordering + 'a,
+    {
+        Search
+        {
+            initial,
+            neighbours: Box::new(move |s| neighbours(s).into_iter().collect()),
+            is_success: Box::new(is_success),
+            compare_successes: Box::new(compare_successes),
+            can_reach_better: Box::new(|_, _| true),
+            frontier: Frontier::Queue,
+            bound: None,
+            is_new: None,
+            debug: SearchDebugLevel::None,
+        }
+    }
+
+    pub fn prune<FR>(mut self, can_state_reach_better_than_solution: FR) -> Self
+        where FR: Fn(&S, &S) -> bool + 'a
+    {
+        self.can_reach_better = Box::new(can_state_reach_better_than_solution);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn breadth_first(mut self) -> Self
+    {
+        self.frontier = Frontier::Queue;
+        self.bound = None;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn depth_first(mut self) -> Self
+    {
+        self.frontier = Frontier::Stack;
+        self.bound = None;
+        self
+    }
+
+    pub fn best_first<FB>(mut self, bound: FB) -> Self
+        where FB: Fn(&S) -> i64 + 'a
     {
-        let prev = to_search.pop_front().unwrap();
+        // States with the highest bound are expanded first.
+        // The bound must be optimistic - once a state with
+        // the highest bound is pruned, the search stops.
 
-        if prev.generation >= generation
+        self.frontier = Frontier::Heap;
+        self.bound = Some(Box::new(bound));
+        self
+    }
+
+    pub fn dedup<FK, K>(mut self, key: FK) -> Self
+        where
+        FK: Fn(&S) -> K + 'a,
+        K: Hash + Eq + 'a,
+    {
+        // Generated states with an already seen
+        // key are dropped
+
+        let mut seen = HashSet::new();
+        self.is_new = Some(Box::new(move |s| seen.insert(key(s))));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn debug(mut self, debug: SearchDebugLevel) -> Self
+    {
+        self.debug = debug;
+        self
+    }
+
+    pub fn run(self) -> SearchResult<S>
+    {
+        let Search { initial, neighbours, is_success, compare_successes, can_reach_better, frontier, bound, mut is_new, debug } = self;
+
+        let mut best_solution: Option<S> = None;
+        let mut stats = SearchStats::default();
+        let mut depth = 0;
+
+        let mut queue = VecDeque::new();
+        let mut heap = BinaryHeap::new();
+
+        if let Some(is_new) = is_new.as_mut()
         {
-            generation = prev.generation + 1;
+            is_new(&initial);
+        }
 
-            if debug >= SearchDebugLevel::Solutions
-            {
-                println!("Starting generation {} with {} generated, {} abandoned, {} queued and {} solution",
-                    generation,
-                    pretty_print(total_generated),
-                    pretty_print(total_abandoned),
-                    pretty_print(to_search.len() as u64),
-                    if best_solution.is_some() { "a" } else { "no" });
-            }
+        match &bound
+        {
+            Some(b) => heap.push(Bounded { bound: b(&initial), depth: 0, state: initial }),
+            None => queue.push_back((0, initial)),
         }
 
-        if is_success(&prev.state)
+        loop
         {
-            match &best_solution
+            let (prev_depth, prev) = match frontier
             {
-                None =>
+                Frontier::Queue => queue.pop_front(),
+                Frontier::Stack => queue.pop_back(),
+                Frontier::Heap => heap.pop().map(|b| (b.depth, b.state)),
+            }
+            .map_or((0, None), |(d, s)| (d, Some(s)));
+
+            let prev = match prev
+            {
+                Some(p) => p,
+                None => break,
+            };
+
+            if (frontier == Frontier::Queue) && (prev_depth >= depth)
+            {
+                depth = prev_depth + 1;
+
+                if debug >= SearchDebugLevel::Solutions
+                {
+                    println!("Starting generation {} with {} generated, {} abandoned, {} queued and {} solution",
+                        depth,
+                        pretty_print(stats.generated),
+                        pretty_print(stats.abandoned),
+                        pretty_print(queue.len() as u64),
+                        if best_solution.is_some() { "a" } else { "no" });
+                }
+            }
+
+            if is_success(&prev)
+            {
+                let is_better = match &best_solution
+                {
+                    None => true,
+                    Some(cur_best) => compare_successes(&prev, cur_best) == Ordering::Less,
+                };
+
+                if is_better
                 {
                     if debug >= SearchDebugLevel::Solutions
                     {
-                        println!("    Found first solution: {:?}", prev.state);
+                        println!("    Found solution: {:?}", prev);
                     }
 
-                    best_solution = Some(prev.state);
-                },
-                Some(cur_best) =>
+                    best_solution = Some(prev);
+                }
+                else
+                {
+                    stats.rejected_solutions += 1;
+                }
+            }
+            else
+            {
+                let should_generate = match &best_solution
+                {
+                    None => true,
+                    Some(best_sol) => can_reach_better(&prev, best_sol),
+                };
+
+                if should_generate
                 {
-                    if compare_successes(&prev.state, cur_best) == Ordering::Less
+                    stats.expanded += 1;
+
+                    if debug >= SearchDebugLevel::Everything
                     {
-                        if debug >= SearchDebugLevel::Solutions
+                        println!("    Expanding: {:?}", prev);
+                    }
+
+                    for s in neighbours(&prev)
+                    {
+                        stats.generated += 1;
+
+                        if let Some(is_new) = is_new.as_mut()
                         {
-                            println!("   Found better solution: {:?}", prev.state);
+                            if !is_new(&s)
+                            {
+                                stats.duplicates += 1;
+                                continue;
+                            }
                         }
 
-                        best_solution = Some(prev.state);
+                        match &bound
+                        {
+                            Some(b) => heap.push(Bounded { bound: b(&s), depth: prev_depth + 1, state: s }),
+                            None => queue.push_back((prev_depth + 1, s)),
+                        }
                     }
-                    else
+                }
+                else
+                {
+                    stats.abandoned += 1;
+
+                    if frontier == Frontier::Heap
                     {
-                        rejected_solutions += 1;
+                        // Nothing left can have a better bound
+
+                        stats.abandoned += heap.len() as u64;
+                        break;
                     }
-                },
+                }
             }
         }
-        else
-        {
-            let should_generate = match &best_solution
-            {
-                None => true,
-                Some(best_sol) => can_state_reach_better_than_solution(&prev.state, best_sol),
-            };
 
-            if should_generate
-            {
-                to_search.extend(neighbours(&prev.state)
-                    .into_iter()
-                    .map(|s|
-                    {
-                        total_generated += 1;
-                        StateCompare { cmp: prev.cmp, generation: prev.generation + 1, state: s}
-                    }));
-            }
-            else
-            {
-                total_abandoned += 1;
-            }
+        if debug >= SearchDebugLevel::FinalStats
+        {
+            println!("Result: {:?}", best_solution);
+            println!("   States expanded: {}", pretty_print(stats.expanded));
+            println!("   Total states generated: {}", pretty_print(stats.generated));
+            println!("   Duplicate states: {}", pretty_print(stats.duplicates));
+            println!("   Rejected solutions: {}", pretty_print(stats.rejected_solutions));
+            println!("   Abandoned states: {}", pretty_print(stats.abandoned));
         }
-    }
 
-    if debug >= SearchDebugLevel::FinalStats
-    {
-        println!("Result: {:?}", best_solution);
-        println!("   Total states generated: {}", pretty_print(total_generated));
-        println!("   Rejected solutions: {}", pretty_print(rejected_solutions));
-        println!("   Abandoned states: {}", pretty_print(total_abandoned));
+        SearchResult { best: best_solution, stats }
     }
-
-    best_solution
 }
 
-struct StateCompare<'a, S, FC>
-    where FC: Fn(&S, &S) -> Ordering
+struct Bounded<S>
 {
-    cmp: &'a FC,
-    generation: u64,
+    // Heap entries ordered only by their bound
+
+    bound: i64,
+    depth: u64,
     state: S,
 }
 
-impl<'a, S, FC> PartialEq for StateCompare<'a, S, FC>
-    where FC: Fn(&S, &S) -> Ordering
+impl<S> PartialEq for Bounded<S>
 {
     fn eq(&self, other: &Self) -> bool
     {
-        (self.cmp)(&self.state, &other.state) == Ordering::Equal
+        self.bound == other.bound
     }
 }
 
-impl <'a, S, FC> Eq for StateCompare<'a, S, FC>
-    where FC: Fn(&S, &S) -> Ordering
+impl<S> Eq for Bounded<S>
 {
 }
 
-impl<'a, S, FC> PartialOrd for StateCompare<'a, S, FC>
-    where FC: Fn(&S, &S) -> Ordering
+impl<S> PartialOrd for Bounded<S>
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering>
     {
@@ -157,12 +309,11 @@ impl<'a, S, FC> PartialOrd for StateCompare<'a, S, FC>
     }
 }
 
-impl<'a, S, FC> Ord for StateCompare<'a, S, FC>
-    where FC: Fn(&S, &S) -> Ordering
+impl<S> Ord for Bounded<S>
 {
     fn cmp(&self, other: &Self) -> Ordering
     {
-        (self.cmp)(&self.state, &other.state)
+        self.bound.cmp(&other.bound)
     }
 }
 
@@ -174,9 +325,70 @@ fn pretty_print(stat: u64) -> String
     while stat > 1000
     {
         parts.insert(0, format!("{:03}", stat % 1000));
-        stat = stat / 1000;
+        stat /= 1000;
     }
     parts.insert(0, stat.to_string());
 
     parts.join(",")
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    // Choose 10 bits to make the largest number
+
+    fn bits_search<'a>() -> Search<'a, (u64, i64)>
+    {
+        Search::new(
+            (0u64, 0i64),
+            |&(depth, value)| vec![(depth + 1, value * 2), (depth + 1, value * 2 + 1)],
+            |&(depth, _)| depth == 10,
+            |a, b| b.1.cmp(&a.1))
+            .prune(|&(depth, value), best| ((value + 1) << (10 - depth)) - 1 > best.1)
+    }
+
+    #[test]
+    fn test_search_strategies()
+    {
+        let bfs = bits_search().breadth_first().run();
+        let dfs = bits_search().depth_first().run();
+        let best = bits_search().best_first(|&(depth, value)| ((value + 1) << (10 - depth)) - 1).run();
+
+        assert_eq!(bfs.best, Some((10, 1023)));
+        assert_eq!(dfs.best, Some((10, 1023)));
+        assert_eq!(best.best, Some((10, 1023)));
+
+        assert_eq!(bfs.stats.expanded, 1023);
+        assert_eq!(best.stats.expanded, 10);
+        assert!(best.stats.abandoned > 0);
+    }
+
+    #[test]
+    fn test_search_dedup()
+    {
+        // Fewest steps of 1 or 2 to reach 10
+
+        let run = |dedup: bool|
+        {
+            let search = Search::new(
+                (0, 0),
+                |&(pos, steps)| vec![(pos + 1, steps + 1), (pos + 2, steps + 1)].into_iter().filter(|s| s.0 <= 10),
+                |&(pos, _)| pos == 10,
+                |a: &(i64, i64), b: &(i64, i64)| a.1.cmp(&b.1))
+                .prune(|s, best| s.1 + 1 < best.1);
+
+            if dedup { search.dedup(|s| s.0).run() } else { search.run() }
+        };
+
+        let plain = run(false);
+        let deduped = run(true);
+
+        assert_eq!(plain.best, Some((10, 5)));
+        assert_eq!(deduped.best, Some((10, 5)));
+        assert_eq!(plain.stats.duplicates, 0);
+        assert!(deduped.stats.duplicates > 0);
+        assert!(deduped.stats.expanded < plain.stats.expanded);
+    }
+}
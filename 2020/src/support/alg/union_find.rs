@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone, Debug)]
+pub struct DenseUnionFind
+{
+    // Sets of the indices 0..len, with path
+    // compression and union by rank
+
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    num_sets: usize,
+}
+
+impl DenseUnionFind
+{
+    pub fn new(len: usize) -> Self
+    {
+        DenseUnionFind
+        {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+            num_sets: len,
+        }
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.parent.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool
+    {
+        self.parent.is_empty()
+    }
+
+    #[allow(dead_code)]
+    pub fn add(&mut self) -> usize
+    {
+        // A new set containing just the new index
+
+        self.parent.push(self.parent.len());
+        self.rank.push(0);
+        self.size.push(1);
+        self.num_sets += 1;
+        self.parent.len() - 1
+    }
+
+    pub fn find(&mut self, index: usize) -> usize
+    {
+        let mut root = index;
+
+        while self.parent[root] != root
+        {
+            root = self.parent[root];
+        }
+
+        let mut cur = index;
+
+        while self.parent[cur] != root
+        {
+            let next = self.parent[cur];
+            self.parent[cur] = root;
+            cur = next;
+        }
+
+        root
+    }
+
+    pub fn union(&mut self, a: usize, b: usize) -> bool
+    {
+        // Returns false if they were already
+        // in the same set
+
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a == b
+        {
+            return false;
+        }
+
+        let (big, small) = if self.rank[a] >= self.rank[b] { (a, b) } else { (b, a) };
+
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+
+        if self.rank[big] == self.rank[small]
+        {
+            self.rank[big] += 1;
+        }
+
+        self.num_sets -= 1;
+        true
+    }
+
+    #[allow(dead_code)]
+    pub fn same(&mut self, a: usize, b: usize) -> bool
+    {
+        self.find(a) == self.find(b)
+    }
+
+    #[allow(dead_code)]
+    pub fn size(&mut self, index: usize) -> usize
+    {
+        let root = self.find(index);
+        self.size[root]
+    }
+
+    pub fn num_sets(&self) -> usize
+    {
+        self.num_sets
+    }
+
+    pub fn components(&mut self) -> Vec<Vec<usize>>
+    {
+        // Ordered by their lowest index, and
+        // each in increasing order
+
+        let mut ids = HashMap::new();
+        let mut result: Vec<Vec<usize>> = Vec::new();
+
+        for i in 0..self.len()
+        {
+            let root = self.find(i);
+            let id = *ids.entry(root).or_insert_with(|| { result.push(Vec::new()); result.len() - 1 });
+
+            result[id].push(i);
+        }
+
+        result
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UnionFind<T>
+    where T: Hash + Eq + Clone,
+{
+    // Values are added the first time they're seen
+
+    indices: HashMap<T, usize>,
+    values: Vec<T>,
+    sets: DenseUnionFind,
+}
+
+impl<T> UnionFind<T>
+    where T: Hash + Eq + Clone,
+{
+    pub fn new() -> Self
+    {
+        UnionFind { indices: HashMap::new(), values: Vec::new(), sets: DenseUnionFind::new(0) }
+    }
+
+    #[allow(dead_code)]
+    pub fn insert(&mut self, value: T) -> usize
+    {
+        if let Some(index) = self.indices.get(&value)
+        {
+            return *index;
+        }
+
+        let index = self.sets.add();
+        self.values.push(value.clone());
+        self.indices.insert(value, index);
+        index
+    }
+
+    #[allow(dead_code)]
+    pub fn find(&mut self, value: &T) -> Option<&T>
+    {
+        // The representative value of the set
+
+        let index = *self.indices.get(value)?;
+        let root = self.sets.find(index);
+
+        Some(&self.values[root])
+    }
+
+    #[allow(dead_code)]
+    pub fn union(&mut self, a: T, b: T) -> bool
+    {
+        let a = self.insert(a);
+        let b = self.insert(b);
+
+        self.sets.union(a, b)
+    }
+
+    #[allow(dead_code)]
+    pub fn same(&mut self, a: &T, b: &T) -> bool
+    {
+        match (self.indices.get(a), self.indices.get(b))
+        {
+            (Some(a), Some(b)) => { let (a, b) = (*a, *b); self.sets.same(a, b) },
+            _ => a == b,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn size(&mut self, value: &T) -> usize
+    {
+        match self.indices.get(value)
+        {
+            Some(index) => { let index = *index; self.sets.size(index) },
+            None => 0,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn num_sets(&self) -> usize
+    {
+        self.sets.num_sets()
+    }
+
+    #[allow(dead_code)]
+    pub fn components(&mut self) -> Vec<Vec<T>>
+    {
+        // Ordered by when the first value
+        // in each was inserted
+
+        self.sets.components()
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| self.values[i].clone()).collect())
+            .collect()
+    }
+}
+
+impl<T> Default for UnionFind<T>
+    where T: Hash + Eq + Clone,
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UndoUnionFind
+{
+    // Without path compression, each union only
+    // changes one parent - so it can be undone by
+    // remembering which root was joined to which
+
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    num_sets: usize,
+    history: Vec<Option<(usize, usize, bool)>>,
+}
+
+impl UndoUnionFind
+{
+    #[allow(dead_code)]
+    pub fn new(len: usize) -> Self
+    {
+        UndoUnionFind
+        {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+            num_sets: len,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn find(&self, index: usize) -> usize
+    {
+        let mut root = index;
+
+        while self.parent[root] != root
+        {
+            root = self.parent[root];
+        }
+
+        root
+    }
+
+    #[allow(dead_code)]
+    pub fn union(&mut self, a: usize, b: usize) -> bool
+    {
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a == b
+        {
+            self.history.push(None);
+            return false;
+        }
+
+        let (big, small) = if self.rank[a] >= self.rank[b] { (a, b) } else { (b, a) };
+        let rank_increased = self.rank[big] == self.rank[small];
+
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+
+        if rank_increased
+        {
+            self.rank[big] += 1;
+        }
+
+        self.num_sets -= 1;
+        self.history.push(Some((big, small, rank_increased)));
+        true
+    }
+
+    #[allow(dead_code)]
+    pub fn same(&self, a: usize, b: usize) -> bool
+    {
+        self.find(a) == self.find(b)
+    }
+
+    #[allow(dead_code)]
+    pub fn size(&self, index: usize) -> usize
+    {
+        self.size[self.find(index)]
+    }
+
+    #[allow(dead_code)]
+    pub fn num_sets(&self) -> usize
+    {
+        self.num_sets
+    }
+
+    #[allow(dead_code)]
+    pub fn snapshot(&self) -> usize
+    {
+        self.history.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn rollback(&mut self, snapshot: usize)
+    {
+        // Undoes every union since the snapshot
+
+        while self.history.len() > snapshot
+        {
+            if let Some((big, small, rank_increased)) = self.history.pop().unwrap()
+            {
+                self.parent[small] = small;
+                self.size[big] -= self.size[small];
+
+                if rank_increased
+                {
+                    self.rank[big] -= 1;
+                }
+
+                self.num_sets += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_union_find()
+    {
+        let mut sets = UnionFind::new();
+
+        assert!(sets.union("a", "b"));
+        assert!(sets.union("c", "d"));
+        assert!(sets.union("b", "e"));
+        assert!(!sets.union("e", "a"));
+        sets.insert("f");
+
+        assert_eq!(sets.num_sets(), 3);
+        assert!(sets.same(&"a", &"e"));
+        assert!(!sets.same(&"a", &"c"));
+        assert_eq!(sets.size(&"e"), 3);
+        let root = sets.find(&"a").copied();
+        assert_eq!(sets.find(&"b").copied(), root);
+        assert_eq!(sets.find(&"x"), None);
+        assert_eq!(sets.components(), vec![vec!["a", "b", "e"], vec!["c", "d"], vec!["f"]]);
+    }
+
+    #[test]
+    fn test_undo_union_find()
+    {
+        let mut sets = UndoUnionFind::new(6);
+
+        sets.union(0, 1);
+        sets.union(2, 3);
+
+        let snapshot = sets.snapshot();
+
+        sets.union(1, 3);
+        sets.union(0, 2);
+        sets.union(4, 5);
+
+        assert_eq!(sets.num_sets(), 2);
+        assert_eq!(sets.size(3), 4);
+
+        sets.rollback(snapshot);
+
+        assert_eq!(sets.num_sets(), 4);
+        assert!(sets.same(0, 1) && sets.same(2, 3));
+        assert!(!sets.same(1, 2) && !sets.same(4, 5));
+        assert_eq!(sets.size(0), 2);
+    }
+}
@@ -63,21 +63,9 @@ impl CharGrid
 
     pub fn new_from_points(points: Vec<Point>) -> Self
     {
-        let min_x = points.iter().map(|p| p.x).min().unwrap();
-        let max_x = points.iter().map(|p| p.x).max().unwrap();
-        let min_y = points.iter().map(|p| p.y).min().unwrap();
-        let max_y = points.iter().map(|p| p.y).max().unwrap();
+        assert!(!points.is_empty());
 
-        let mut image = CharGrid::new_from_fill((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize, '.');
-
-        for p in points
-        {
-            image.put_char(
-                &Point::new(p.x - min_x, p.y - min_y),
-                '#');
-        }
-
-        image
+        SparseGrid::new_from_points(points, '#').to_char_grid('.', |&c| c)
     }
 
     #[allow(dead_code)]
@@ -140,6 +128,7 @@ impl CharGrid
         self.chars.clone()
     }
 
+    #[allow(dead_code)]
     pub fn rotate_cw_90(&self) -> CharGrid
     {
         let mut result = CharGrid::new_from_fill(self.height as usize, self.width as usize, self.default);
@@ -156,6 +145,7 @@ impl CharGrid
         result
     }
 
+    #[allow(dead_code)]
     pub fn flip_horizontally(&self) -> CharGrid
     {
         let mut result = CharGrid::new_from_fill(self.width as usize, self.height as usize, self.default);
@@ -172,6 +162,82 @@ impl CharGrid
         result
     }
 
+    pub fn all_orientations(&self) -> Vec<(Transform2, CharGrid)>
+    {
+        // Each of the 8 rotations and reflections, with
+        // the transform mapping points in this grid to
+        // the same char in the reoriented grid
+
+        Transform2::rotations_and_reflections_8()
+            .map(|t| (t.fit_to_grid(self), t.transform_grid(self)))
+            .collect()
+    }
+
+    pub fn sub_grid(&self, rect: Rect) -> CharGrid
+    {
+        // The (inclusive) rect is copied to a new
+        // grid starting at (0, 0)
+
+        let mut result = CharGrid::new_from_fill(
+            (rect.get_max_x() - rect.get_min_x() + 1) as usize,
+            (rect.get_max_y() - rect.get_min_y() + 1) as usize,
+            self.default);
+
+        let offset = Point::new(rect.get_min_x(), rect.get_min_y());
+
+        for p in result.all_points()
+        {
+            result.put_char(&p, self.get_char(&(p + offset)));
+        }
+
+        result
+    }
+
+    pub fn put_grid(&mut self, offset: &Point, grid: &CharGrid)
+    {
+        for p in grid.all_points()
+        {
+            self.put_char(&(p + *offset), grid.get_char(&p));
+        }
+    }
+
+    pub fn new_from_tiles(tiles: &[Vec<CharGrid>]) -> Self
+    {
+        // Stitches rows of equally sized tiles
+        // together, with no gap between them
+
+        assert!(!tiles.is_empty() && !tiles[0].is_empty());
+
+        let tile_width = tiles[0][0].width;
+        let tile_height = tiles[0][0].height;
+
+        assert!(tiles.iter().flatten().all(|t| (t.width == tile_width) && (t.height == tile_height)));
+        assert!(tiles.iter().all(|row| row.len() == tiles[0].len()));
+
+        let mut result = CharGrid::new_from_fill(
+            tiles[0].len() * (tile_width as usize),
+            tiles.len() * (tile_height as usize),
+            tiles[0][0].default);
+
+        for (y, row) in tiles.iter().enumerate()
+        {
+            for (x, tile) in row.iter().enumerate()
+            {
+                result.put_grid(&Point::new(x as i64 * tile_width, y as i64 * tile_height), tile);
+            }
+        }
+
+        result
+    }
+
+    pub fn walkable_except<'a>(&'a self, walls: &'a str) -> impl Fn(&Point) -> bool + 'a
+    {
+        // For the grid search routines - in-bounds points that
+        // are not one of the wall characters are walkable
+
+        move |p| self.is_point_in_bounds(p) && !walls.contains(self.get_char(p))
+    }
+
     #[allow(unused)]
     pub fn find_inside_point(&self) -> Point
     {
@@ -260,4 +326,21 @@ mod tests
         assert_eq!(CharGrid::new_from_input("12\n34\n56", ' ').to_string(), "12\n34\n56");
         assert_eq!(CharGrid::new_from_fill(3, 2, '*').to_string(), "***\n***");
     }
+
+    #[test]
+    fn test_char_grid_tiles()
+    {
+        let grid = CharGrid::new_from_input("1234\n5678\nabcd\nefgh", ' ');
+
+        assert_eq!(grid.sub_grid(Rect::new_from_coords(1, 1, 2, 3)).to_string(), "67\nbc\nfg");
+
+        let tiles = (0..2)
+            .map(|y| (0..2)
+                .map(|x| grid.sub_grid(Rect::new_from_coords(2 * x, 2 * y, 2 * x + 1, 2 * y + 1)))
+                .collect())
+            .collect::<Vec<Vec<_>>>();
+
+        assert_eq!(tiles[1][0].to_string(), "ab\nef");
+        assert_eq!(CharGrid::new_from_tiles(&tiles).to_string(), grid.to_string());
+    }
 }
@@ -0,0 +1,302 @@
+use crate::support::chargrid::CharGrid;
+use crate::support::geom::{Point, PointN};
+
+// Sides and directions are numbered clockwise
+// from the right: 0 = Right, 1 = Down, 2 = Left, 3 = Up
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CubeEdge
+{
+    // Leaving a face across an edge arrives on this
+    // face, travelling in this direction. If reversed,
+    // the position along the edge is flipped.
+
+    pub face: usize,
+    pub dir: usize,
+    pub reversed: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct CubeNet
+{
+    face_size: i64,
+    faces: Vec<Point>,
+    folded: Vec<[CubeEdge; 4]>,
+    wrapped: Vec<[CubeEdge; 4]>,
+}
+
+#[derive(Copy, Clone)]
+struct FaceAxes
+{
+    normal: PointN<3>,
+    right: PointN<3>,
+    down: PointN<3>,
+}
+
+impl FaceAxes
+{
+    fn side_dir(&self, side: usize) -> PointN<3>
+    {
+        match side
+        {
+            0 => self.right,
+            1 => self.down,
+            2 => -self.right,
+            _ => -self.down,
+        }
+    }
+
+    fn along_side(&self, side: usize) -> PointN<3>
+    {
+        // The direction of increasing position along
+        // a side - y for left and right, x otherwise
+
+        match side
+        {
+            0 | 2 => self.down,
+            _ => self.right,
+        }
+    }
+
+    fn fold(&self, side: usize) -> FaceAxes
+    {
+        // The neighbouring face in the net, folded
+        // away from the viewer onto the cube
+
+        let (n, r, d) = (self.normal, self.right, self.down);
+
+        match side
+        {
+            0 => FaceAxes { normal: r, right: -n, down: d },
+            1 => FaceAxes { normal: d, right: r, down: -n },
+            2 => FaceAxes { normal: -r, right: n, down: d },
+            _ => FaceAxes { normal: -d, right: r, down: n },
+        }
+    }
+}
+
+fn side_offset(side: usize) -> Point
+{
+    match side
+    {
+        0 => Point::new(1, 0),
+        1 => Point::new(0, 1),
+        2 => Point::new(-1, 0),
+        _ => Point::new(0, -1),
+    }
+}
+
+impl CubeNet
+{
+    pub fn new_from_grid(grid: &CharGrid, empty: char) -> Self
+    {
+        // Faces are numbered in reading order
+
+        let num_cells = grid.all_chars().into_iter().filter(|&c| c != empty).count() as i64;
+        let face_size = (1..).find(|s| 6 * s * s >= num_cells).unwrap();
+        assert_eq!(6 * face_size * face_size, num_cells, "Not a cube net");
+
+        let net_width = (grid.get_width() + face_size - 1) / face_size;
+        let net_height = (grid.get_height() + face_size - 1) / face_size;
+
+        let faces = (0..net_height)
+            .flat_map(|y| (0..net_width).map(move |x| Point::new(x, y)))
+            .filter(|p| grid.get_char(&Point::new(p.x * face_size, p.y * face_size)) != empty)
+            .collect::<Vec<_>>();
+
+        assert_eq!(faces.len(), 6, "Not a cube net");
+
+        let find_face = |p: Point| faces.iter().position(|f| *f == p);
+
+        // Fold the faces out from the first one to
+        // find the orientation of each in 3D
+
+        let mut axes: Vec<Option<FaceAxes>> = vec![None; 6];
+        axes[0] = Some(FaceAxes
+        {
+            normal: PointN::new([0, 0, 1]),
+            right: PointN::new([1, 0, 0]),
+            down: PointN::new([0, 1, 0]),
+        });
+
+        let mut stack = vec![0];
+
+        while let Some(face) = stack.pop()
+        {
+            let cur = axes[face].unwrap();
+
+            for side in 0..4
+            {
+                if let Some(next) = find_face(faces[face] + side_offset(side))
+                {
+                    if axes[next].is_none()
+                    {
+                        axes[next] = Some(cur.fold(side));
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+
+        let axes = axes.into_iter().map(|a| a.expect("Not a cube net")).collect::<Vec<_>>();
+
+        let folded = (0..6)
+            .map(|face|
+            {
+                let mut edges = [CubeEdge { face: 0, dir: 0, reversed: false }; 4];
+
+                for (side, edge) in edges.iter_mut().enumerate()
+                {
+                    // The face beyond this side faces outwards in
+                    // this side's direction, and we arrive across
+                    // its side pointing back along our normal
+
+                    let a = axes[face];
+                    let dest = axes.iter().position(|o| o.normal == a.side_dir(side)).unwrap();
+                    let dest_side = (0..4).find(|&s| axes[dest].side_dir(s) == a.normal).unwrap();
+
+                    *edge = CubeEdge
+                    {
+                        face: dest,
+                        dir: (dest_side + 2) % 4,
+                        reversed: a.along_side(side) != axes[dest].along_side(dest_side),
+                    };
+                }
+
+                edges
+            })
+            .collect();
+
+        let wrapped = (0..6)
+            .map(|face|
+            {
+                let mut edges = [CubeEdge { face: 0, dir: 0, reversed: false }; 4];
+
+                for (side, edge) in edges.iter_mut().enumerate()
+                {
+                    // Keep stepping across the net, wrapping
+                    // around, until we hit another face
+
+                    let mut p = faces[face];
+
+                    let dest = loop
+                    {
+                        p += side_offset(side);
+                        p = Point::new(p.x.rem_euclid(net_width), p.y.rem_euclid(net_height));
+
+                        if let Some(f) = find_face(p)
+                        {
+                            break f;
+                        }
+                    };
+
+                    *edge = CubeEdge { face: dest, dir: side, reversed: false };
+                }
+
+                edges
+            })
+            .collect();
+
+        CubeNet { face_size, faces, folded, wrapped }
+    }
+
+    pub fn face_size(&self) -> i64
+    {
+        self.face_size
+    }
+
+    pub fn face_origin(&self, face: usize) -> Point
+    {
+        // The top-left grid point of the face
+
+        Point::new(self.faces[face].x * self.face_size, self.faces[face].y * self.face_size)
+    }
+
+    pub fn folded_edge(&self, face: usize, side: usize) -> CubeEdge
+    {
+        self.folded[face][side]
+    }
+
+    pub fn wrapped_edge(&self, face: usize, side: usize) -> CubeEdge
+    {
+        // Treating the net as flat, and wrapping
+        // around the edges of the grid
+
+        self.wrapped[face][side]
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "##..\n.##.\n..##",
+        "###..\n..###",
+    ];
+
+    #[test]
+    fn test_cube_net_all_nets()
+    {
+        for net in NETS.iter()
+        {
+            let net = CubeNet::new_from_grid(&CharGrid::new_from_input(net, '.'), '.');
+
+            assert_eq!(net.face_size(), 1);
+
+            for face in 0..6
+            {
+                let mut seen = Vec::new();
+
+                for side in 0..4
+                {
+                    // Crossing back over the same edge returns
+                    // to where we started
+
+                    let edge = net.folded_edge(face, side);
+                    let back = net.folded_edge(edge.face, (edge.dir + 2) % 4);
+
+                    assert_ne!(edge.face, face);
+                    assert_eq!(back.face, face);
+                    assert_eq!(back.dir, (side + 2) % 4);
+                    assert_eq!(back.reversed, edge.reversed);
+
+                    seen.push(edge.face);
+                }
+
+                seen.sort_unstable();
+                seen.dedup();
+                assert_eq!(seen.len(), 4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cube_net_example()
+    {
+        // 2022 day 22 example layout
+
+        let grid = CharGrid::new_from_input("..#.\n###.\n..##", '.');
+
+        let net = CubeNet::new_from_grid(&grid, '.');
+
+        assert_eq!(net.face_size(), 1);
+        assert_eq!(net.face_origin(5), Point::new(3, 2));
+        assert_eq!(net.folded_edge(0, 0), CubeEdge { face: 5, dir: 2, reversed: true });
+        assert_eq!(net.folded_edge(0, 2), CubeEdge { face: 2, dir: 1, reversed: false });
+        assert_eq!(net.folded_edge(3, 0), CubeEdge { face: 5, dir: 1, reversed: true });
+        assert_eq!(net.folded_edge(4, 1), CubeEdge { face: 1, dir: 3, reversed: true });
+        assert_eq!(net.wrapped_edge(0, 3), CubeEdge { face: 4, dir: 3, reversed: false });
+        assert_eq!(net.wrapped_edge(1, 2), CubeEdge { face: 3, dir: 2, reversed: false });
+    }
+}
@@ -25,6 +25,23 @@ impl Cuboid
         Cuboid { min, max }
     }
 
+    pub fn get_min(&self) -> Point3
+    {
+        self.min
+    }
+
+    pub fn get_max(&self) -> Point3
+    {
+        self.max
+    }
+
+    pub fn does_point_intersect(&self, p: Point3) -> bool
+    {
+        (p.x >= self.min.x) && (p.x <= self.max.x)
+        && (p.y >= self.min.y) && (p.y <= self.max.y)
+        && (p.z >= self.min.z) && (p.z <= self.max.z)
+    }
+
     pub fn volume(&self) -> i64
     {
         (self.max.x - self.min.x + 1)
@@ -0,0 +1,266 @@
+use crate::support::geom::{Cuboid, Point3, PointN};
+
+#[derive(Clone, Debug)]
+pub struct CuboidSet
+{
+    // The cuboids are always kept disjoint, so the
+    // volume is simply the sum of their volumes.
+    // Repeated inserts and removes fragment the set -
+    // compact() merges adjacent fragments back together.
+
+    cuboids: Vec<Cuboid>,
+}
+
+impl CuboidSet
+{
+    pub fn new() -> Self
+    {
+        CuboidSet { cuboids: Vec::new() }
+    }
+
+    #[allow(dead_code)]
+    pub fn new_from_cuboid(cuboid: Cuboid) -> Self
+    {
+        CuboidSet { cuboids: vec![cuboid] }
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool
+    {
+        self.cuboids.is_empty()
+    }
+
+    pub fn volume(&self) -> i64
+    {
+        self.cuboids.iter().map(|c| c.volume()).sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn contains_point(&self, point: Point3) -> bool
+    {
+        self.cuboids.iter().any(|c| c.does_point_intersect(point))
+    }
+
+    #[allow(dead_code)]
+    pub fn cuboids(&self) -> impl Iterator<Item = Cuboid> + '_
+    {
+        self.cuboids.iter().copied()
+    }
+
+    pub fn insert_cuboid(&mut self, cuboid: Cuboid)
+    {
+        // Cut the new cuboid out of the existing ones
+        // so that they stay disjoint
+
+        self.remove_cuboid(cuboid);
+        self.cuboids.push(cuboid);
+    }
+
+    #[allow(dead_code)]
+    pub fn insert_set(&mut self, set: &CuboidSet)
+    {
+        for c in set.cuboids.iter()
+        {
+            self.insert_cuboid(*c);
+        }
+    }
+
+    pub fn remove_cuboid(&mut self, cuboid: Cuboid)
+    {
+        self.cuboids = self.cuboids.iter()
+            .filter_map(|existing| existing.difference(cuboid))
+            .flatten()
+            .collect();
+    }
+
+    #[allow(dead_code)]
+    pub fn remove_set(&mut self, set: &CuboidSet)
+    {
+        for c in set.cuboids.iter()
+        {
+            self.remove_cuboid(*c);
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn intersect_with_cuboid(&mut self, cuboid: Cuboid)
+    {
+        self.cuboids = self.cuboids.iter()
+            .filter_map(|existing| existing.intersection(cuboid))
+            .collect();
+    }
+
+    #[allow(dead_code)]
+    pub fn intersection(&self, other: &CuboidSet) -> CuboidSet
+    {
+        // Both sets are disjoint, so the pairwise
+        // intersections are also disjoint
+
+        let cuboids = self.cuboids.iter()
+            .flat_map(|a| other.cuboids.iter().filter_map(move |b| a.intersection(*b)))
+            .collect();
+
+        CuboidSet { cuboids }
+    }
+
+    #[allow(dead_code)]
+    pub fn compact(&mut self)
+    {
+        // Repeatedly merge any pair of cuboids that share
+        // a complete face, until no more can be merged
+
+        'outer: loop
+        {
+            for i in 0..self.cuboids.len()
+            {
+                for j in (i + 1)..self.cuboids.len()
+                {
+                    if let Some(merged) = try_merge(self.cuboids[i], self.cuboids[j])
+                    {
+                        self.cuboids[i] = merged;
+                        self.cuboids.swap_remove(j);
+                        continue 'outer;
+                    }
+                }
+            }
+
+            return;
+        }
+    }
+}
+
+fn try_merge(a: Cuboid, b: Cuboid) -> Option<Cuboid>
+{
+    let a_min = PointN::from(a.get_min()).coords;
+    let a_max = PointN::from(a.get_max()).coords;
+    let b_min = PointN::from(b.get_min()).coords;
+    let b_max = PointN::from(b.get_max()).coords;
+
+    for axis in 0..3
+    {
+        let others_match = (0..3)
+            .filter(|&other| other != axis)
+            .all(|other| (a_min[other] == b_min[other]) && (a_max[other] == b_max[other]));
+
+        let adjacent = (a_max[axis] + 1 == b_min[axis]) || (b_max[axis] + 1 == a_min[axis]);
+
+        if others_match && adjacent
+        {
+            return Some(Cuboid::new(
+                Point3::new(a_min[0].min(b_min[0]), a_min[1].min(b_min[1]), a_min[2].min(b_min[2])),
+                Point3::new(a_max[0].max(b_max[0]), a_max[1].max(b_max[1]), a_max[2].max(b_max[2]))));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use std::collections::HashSet;
+
+    fn cuboid(x1: i64, y1: i64, z1: i64, x2: i64, y2: i64, z2: i64) -> Cuboid
+    {
+        Cuboid::new(Point3::new(x1, y1, z1), Point3::new(x2, y2, z2))
+    }
+
+    fn voxels(c: Cuboid) -> HashSet<Point3>
+    {
+        let min = PointN::from(c.get_min());
+        let max = PointN::from(c.get_max());
+
+        PointN::points_in_box(min, max).map(Point3::from).collect()
+    }
+
+    fn check_matches(set: &CuboidSet, expected: &HashSet<Point3>)
+    {
+        assert_eq!(set.volume(), expected.len() as i64);
+
+        for p in PointN::points_in_box(PointN::new([-1; 3]), PointN::new([7; 3])).map(Point3::from)
+        {
+            assert_eq!(set.contains_point(p), expected.contains(&p));
+        }
+    }
+
+    #[test]
+    fn test_cuboid_set_compact()
+    {
+        let mut set = CuboidSet::new_from_cuboid(cuboid(0, 0, 0, 9, 9, 9));
+        set.remove_cuboid(cuboid(4, 4, 4, 5, 5, 5));
+        set.insert_cuboid(cuboid(4, 4, 4, 5, 5, 5));
+
+        assert_eq!(set.volume(), 1000);
+        assert!(set.cuboids().count() > 1);
+
+        set.compact();
+        assert_eq!(set.cuboids().collect::<Vec<_>>(), vec![cuboid(0, 0, 0, 9, 9, 9)]);
+
+        // Touching only along an edge can't be merged
+
+        let mut set = CuboidSet::new_from_cuboid(cuboid(0, 0, 0, 1, 1, 1));
+        set.insert_cuboid(cuboid(2, 2, 0, 3, 3, 1));
+        set.compact();
+        assert_eq!(set.cuboids().count(), 2);
+    }
+
+    #[test]
+    fn test_cuboid_set_against_voxels()
+    {
+        // Random operations on small bounds, checked
+        // against a brute-force set of voxels
+
+        let mut seed = 0x2545f4914f6cdd1du64;
+        let mut next = |max: i64| -> i64
+        {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % (max as u64)) as i64
+        };
+
+        for _ in 0..100
+        {
+            let mut set = CuboidSet::new();
+            let mut expected = HashSet::new();
+
+            for _ in 0..8
+            {
+                let c = cuboid(next(7), next(7), next(7), next(7), next(7), next(7));
+
+                match next(5)
+                {
+                    0 | 1 =>
+                    {
+                        set.insert_cuboid(c);
+                        expected.extend(voxels(c));
+                    },
+                    2 =>
+                    {
+                        set.remove_cuboid(c);
+                        expected = expected.difference(&voxels(c)).copied().collect();
+                    },
+                    3 =>
+                    {
+                        set.intersect_with_cuboid(c);
+                        expected.retain(|p| c.does_point_intersect(*p));
+                    },
+                    _ =>
+                    {
+                        let mut other = CuboidSet::new_from_cuboid(c);
+                        other.insert_cuboid(cuboid(0, 0, 0, 3, 3, 3));
+
+                        set = set.intersection(&other);
+                        expected.retain(|p| c.does_point_intersect(*p) || (p.x <= 3 && p.y <= 3 && p.z <= 3));
+                    },
+                }
+
+                check_matches(&set, &expected);
+            }
+
+            set.compact();
+            check_matches(&set, &expected);
+        }
+    }
+}
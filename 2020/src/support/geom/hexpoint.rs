@@ -0,0 +1,328 @@
+use crate::support::geom::point::Point;
+use crate::support::sparsegrid::SparseGrid;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HexPoint
+{
+    // Axial co-ordinates - the third cube
+    // co-ordinate is implied by q + r + s = 0.
+    // Positive r is "down" when rendered.
+
+    pub q: i64,
+    pub r: i64,
+}
+
+// The six directions, anti-clockwise starting
+// from positive q
+
+const HEX_DIRS: [(i64, i64); 6] = [
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+];
+
+impl HexPoint
+{
+    pub fn new(q: i64, r: i64) -> Self
+    {
+        HexPoint { q, r }
+    }
+
+    pub fn origin() -> Self
+    {
+        HexPoint { q: 0, r: 0 }
+    }
+
+    #[allow(dead_code)]
+    pub fn new_from_cube(q: i64, r: i64, s: i64) -> Self
+    {
+        assert!(q + r + s == 0);
+
+        HexPoint { q, r }
+    }
+
+    pub fn s(&self) -> i64
+    {
+        -self.q - self.r
+    }
+
+    pub fn size(&self) -> i64
+    {
+        (self.q.abs() + self.r.abs() + self.s().abs()) / 2
+    }
+
+    #[allow(dead_code)]
+    pub fn distance(&self, other: &HexPoint) -> i64
+    {
+        (*self - *other).size()
+    }
+
+    pub fn directions() -> impl Iterator<Item = HexPoint>
+    {
+        HEX_DIRS.iter()
+            .map(|(dq, dr)| HexPoint::new(*dq, *dr))
+    }
+
+    pub fn neighbours(&self) -> impl Iterator<Item = HexPoint>
+    {
+        let offset = *self;
+
+        Self::directions().map(move |d| offset + d)
+    }
+
+    #[allow(dead_code)]
+    pub fn ring(&self, radius: i64) -> Vec<HexPoint>
+    {
+        // Walks around the ring, starting from the
+        // point "radius" steps in the last direction
+
+        if radius == 0
+        {
+            return vec![*self];
+        }
+
+        let mut result = Vec::new();
+        let mut cur = *self + HexPoint::new(HEX_DIRS[4].0, HEX_DIRS[4].1) * radius;
+
+        for dir in Self::directions()
+        {
+            for _ in 0..radius
+            {
+                result.push(cur);
+                cur += dir;
+            }
+        }
+
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_60_left(&self) -> HexPoint
+    {
+        // Anti-clockwise about the origin
+
+        HexPoint::new(-self.s(), -self.q)
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_60_right(&self) -> HexPoint
+    {
+        // Clockwise about the origin
+
+        HexPoint::new(-self.r, -self.s())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HexOrientation
+{
+    PointyTop,
+    #[allow(dead_code)]
+    FlatTop,
+}
+
+impl HexOrientation
+{
+    pub fn direction_names(&self) -> [(&'static str, HexPoint); 6]
+    {
+        match self
+        {
+            HexOrientation::PointyTop =>
+            [
+                ("e", HexPoint::new(1, 0)),
+                ("ne", HexPoint::new(1, -1)),
+                ("nw", HexPoint::new(0, -1)),
+                ("w", HexPoint::new(-1, 0)),
+                ("sw", HexPoint::new(-1, 1)),
+                ("se", HexPoint::new(0, 1)),
+            ],
+            HexOrientation::FlatTop =>
+            [
+                ("se", HexPoint::new(1, 0)),
+                ("ne", HexPoint::new(1, -1)),
+                ("n", HexPoint::new(0, -1)),
+                ("nw", HexPoint::new(-1, 0)),
+                ("sw", HexPoint::new(-1, 1)),
+                ("s", HexPoint::new(0, 1)),
+            ],
+        }
+    }
+
+    pub fn parse_direction(&self, name: &str) -> Option<HexPoint>
+    {
+        self.direction_names().iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, d)| *d)
+    }
+
+    pub fn parse_directions(&self, input: &str) -> Vec<HexPoint>
+    {
+        // A run of direction names without separators,
+        // e.g. "esenee". Two letter names are tried first.
+
+        let mut result = Vec::new();
+        let mut remaining = input.trim();
+
+        while !remaining.is_empty()
+        {
+            let two = remaining.get(0..2).and_then(|n| self.parse_direction(n));
+
+            if let Some(dir) = two
+            {
+                result.push(dir);
+                remaining = &remaining[2..];
+            }
+            else
+            {
+                let one = remaining.get(0..1).and_then(|n| self.parse_direction(n));
+
+                match one
+                {
+                    Some(dir) => result.push(dir),
+                    None => panic!("Invalid {:?} hex direction in {:?}", self, input),
+                }
+                remaining = &remaining[1..];
+            }
+        }
+
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn render<I>(&self, cells: I) -> String
+        where I: IntoIterator<Item = (HexPoint, char)>
+    {
+        // Pointy-top hexes are offset by half a cell on
+        // each row, and flat-top hexes are offset by half
+        // a cell in each column. Gaps are left as spaces.
+
+        let grid = cells.into_iter()
+            .map(|(h, ch)|
+            {
+                let p = match self
+                {
+                    HexOrientation::PointyTop => Point::new(2 * h.q + h.r, h.r),
+                    HexOrientation::FlatTop => Point::new(h.q, 2 * h.r + h.q),
+                };
+                (p, ch)
+            })
+            .collect::<SparseGrid<char>>();
+
+        grid.render(' ', |&c| c)
+    }
+}
+
+impl std::fmt::Debug for HexPoint
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        fmt.write_fmt(format_args!("<{}, {}>", self.q, self.r))
+    }
+}
+
+impl std::ops::Add for HexPoint
+{
+    type Output = HexPoint;
+
+    fn add(self, rhs: HexPoint) -> HexPoint
+    {
+        HexPoint::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl std::ops::AddAssign for HexPoint
+{
+    fn add_assign(&mut self, rhs: HexPoint)
+    {
+        self.q += rhs.q;
+        self.r += rhs.r;
+    }
+}
+
+impl std::ops::Sub for HexPoint
+{
+    type Output = HexPoint;
+
+    fn sub(self, rhs: HexPoint) -> HexPoint
+    {
+        HexPoint::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl std::ops::Mul<i64> for HexPoint
+{
+    type Output = HexPoint;
+
+    fn mul(self, rhs: i64) -> HexPoint
+    {
+        HexPoint::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_hex_point_distance_and_rings()
+    {
+        let centre = HexPoint::new(2, -1);
+
+        assert_eq!(centre.s(), -1);
+        assert_eq!(centre.neighbours().count(), 6);
+        assert!(centre.neighbours().all(|n| n.distance(&centre) == 1));
+        assert_eq!(HexPoint::new(3, -5).distance(&HexPoint::new(-1, 2)), 7);
+        assert_eq!(HexPoint::new_from_cube(1, 2, -3), HexPoint::new(1, 2));
+
+        assert_eq!(centre.ring(0), vec![centre]);
+        for radius in 1..5
+        {
+            let ring = centre.ring(radius);
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(ring.iter().all(|p| p.distance(&centre) == radius));
+        }
+    }
+
+    #[test]
+    fn test_hex_point_rotation()
+    {
+        let dirs = HexPoint::directions().collect::<Vec<_>>();
+
+        for i in 0..6
+        {
+            assert_eq!(dirs[i].rotate_60_left(), dirs[(i + 1) % 6]);
+            assert_eq!(dirs[i].rotate_60_right(), dirs[(i + 5) % 6]);
+        }
+
+        let p = HexPoint::new(3, -1);
+        assert_eq!((0..6).fold(p, |acc, _| acc.rotate_60_left()), p);
+        assert_eq!(p.rotate_60_left().size(), p.size());
+    }
+
+    #[test]
+    fn test_hex_orientation_parse()
+    {
+        let pointy = HexOrientation::PointyTop.parse_directions("nwwswee");
+        assert_eq!(pointy.into_iter().fold(HexPoint::origin(), |a, b| a + b), HexPoint::origin());
+
+        let flat = HexOrientation::FlatTop.parse_directions("nnesse");
+        assert_eq!(flat, vec![HexPoint::new(0, -1), HexPoint::new(1, -1), HexPoint::new(0, 1), HexPoint::new(1, 0)]);
+
+        assert_eq!(HexOrientation::FlatTop.parse_direction("e"), None);
+    }
+
+    #[test]
+    fn test_hex_orientation_render()
+    {
+        let cells = HexPoint::origin().ring(1).into_iter()
+            .map(|h| (h, '#'))
+            .chain(std::iter::once((HexPoint::origin(), 'O')));
+
+        assert_eq!(HexOrientation::PointyTop.render(cells.clone()), " # # \n# O #\n # # ");
+        assert_eq!(HexOrientation::FlatTop.render(cells), " # \n# #\n O \n# #\n # ");
+    }
+}
@@ -0,0 +1,390 @@
+use itertools::Itertools;
+use crate::support::geom::{Point, Point3, PointN, Rect};
+use crate::support::alg::RangeInc;
+
+// All points within a Manhattan distance of a centre - a
+// diamond in 2D and an octahedron in 3D.
+//
+// Rotating 2D co-ordinates by 45 degrees (u = x + y, v = x - y)
+// turns a diamond into an axis-aligned square. Only rotated
+// points where u and v have the same parity map back to real
+// points.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ManhattanBall
+{
+    pub centre: Point,
+    pub radius: i64,
+}
+
+impl ManhattanBall
+{
+    pub fn new(centre: Point, radius: i64) -> Self
+    {
+        assert!(radius >= 0);
+
+        ManhattanBall { centre, radius }
+    }
+
+    pub fn new_from_edge(centre: Point, edge: Point) -> Self
+    {
+        ManhattanBall::new(centre, (edge - centre).manhatten_size())
+    }
+
+    pub fn rotate_point(p: Point) -> Point
+    {
+        Point::new(p.x + p.y, p.x - p.y)
+    }
+
+    pub fn unrotate_point(p: Point) -> Option<Point>
+    {
+        if (p.x - p.y).rem_euclid(2) == 0
+        {
+            Some(Point::new((p.x + p.y) / 2, (p.x - p.y) / 2))
+        }
+        else
+        {
+            None
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn contains_point(&self, p: Point) -> bool
+    {
+        (p - self.centre).manhatten_size() <= self.radius
+    }
+
+    #[allow(dead_code)]
+    pub fn num_points(&self) -> i64
+    {
+        2 * self.radius * (self.radius + 1) + 1
+    }
+
+    #[allow(dead_code)]
+    pub fn intersects(&self, other: &ManhattanBall) -> bool
+    {
+        (self.centre - other.centre).manhatten_size() <= self.radius + other.radius
+    }
+
+    pub fn to_rotated_rect(self) -> Rect
+    {
+        let c = Self::rotate_point(self.centre);
+
+        Rect::new_from_coords(c.x - self.radius, c.y - self.radius, c.x + self.radius, c.y + self.radius)
+    }
+
+    #[allow(dead_code)]
+    pub fn intersection(&self, other: &ManhattanBall) -> Option<Rect>
+    {
+        // The overlap of two balls isn't a ball - but it
+        // is a rect in rotated co-ordinates
+
+        self.to_rotated_rect().intersection(other.to_rotated_rect())
+    }
+
+    pub fn x_extent_at_y(&self, y: i64) -> Option<RangeInc<i64>>
+    {
+        let offset = self.radius - (y - self.centre.y).abs();
+
+        if offset >= 0
+        {
+            Some(RangeInc::new_range(self.centre.x - offset, self.centre.x + offset))
+        }
+        else
+        {
+            None
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn boundary_points(&self) -> impl Iterator<Item = Point>
+    {
+        // The points at exactly the radius, walking
+        // anti-clockwise from the right-most point
+
+        let centre = self.centre;
+        let radius = self.radius;
+
+        let count = if radius == 0 { 1 } else { 4 * radius };
+
+        (0..count).map(move |i|
+        {
+            let side = i / radius.max(1);
+            let step = i % radius.max(1);
+
+            let offset = match side
+            {
+                0 => Point::new(radius - step, step),
+                1 => Point::new(-step, radius - step),
+                2 => Point::new(step - radius, -step),
+                _ => Point::new(step, step - radius),
+            };
+
+            centre + offset
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn union_num_points(balls: &[ManhattanBall]) -> i64
+    {
+        Self::num_points_covered_by_at_least(balls, 1)
+    }
+
+    pub fn num_points_covered_by_at_least(balls: &[ManhattanBall], k: usize) -> i64
+    {
+        // Compress the rotated co-ordinates so each cell
+        // is covered by exactly the same balls, and then
+        // count the real points in each sufficiently
+        // covered cell
+
+        let rects = balls.iter().map(|b| b.to_rotated_rect()).collect_vec();
+
+        let us = rects.iter()
+            .flat_map(|r| [r.get_min_x(), r.get_max_x() + 1])
+            .sorted().dedup().collect_vec();
+
+        let vs = rects.iter()
+            .flat_map(|r| [r.get_min_y(), r.get_max_y() + 1])
+            .sorted().dedup().collect_vec();
+
+        let mut result = 0;
+
+        for (u1, u2) in us.iter().tuple_windows()
+        {
+            let active = rects.iter()
+                .filter(|r| (r.get_min_x() <= *u1) && (r.get_max_x() >= *u1))
+                .collect_vec();
+
+            if active.len() < k
+            {
+                continue;
+            }
+
+            for (v1, v2) in vs.iter().tuple_windows()
+            {
+                let coverage = active.iter()
+                    .filter(|r| (r.get_min_y() <= *v1) && (r.get_max_y() >= *v1))
+                    .count();
+
+                if coverage >= k
+                {
+                    result += num_real_points(*u1, *u2 - 1, *v1, *v2 - 1);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+fn num_real_points(u1: i64, u2: i64, v1: i64, v2: i64) -> i64
+{
+    // The number of rotated points with u and v the same parity
+
+    let evens = |a: i64, b: i64| b.div_euclid(2) - (a - 1).div_euclid(2);
+
+    let even_u = evens(u1, u2);
+    let even_v = evens(v1, v2);
+    let odd_u = (u2 - u1 + 1) - even_u;
+    let odd_v = (v2 - v1 + 1) - even_v;
+
+    (even_u * even_v) + (odd_u * odd_v)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ManhattanBall3
+{
+    pub centre: Point3,
+    pub radius: i64,
+}
+
+#[allow(dead_code)]
+impl ManhattanBall3
+{
+    pub fn new(centre: Point3, radius: i64) -> Self
+    {
+        assert!(radius >= 0);
+
+        ManhattanBall3 { centre, radius }
+    }
+
+    pub fn contains_point(&self, p: Point3) -> bool
+    {
+        (p - self.centre).manhatten_size() <= self.radius
+    }
+
+    pub fn num_points(&self) -> i64
+    {
+        let r = self.radius;
+
+        (2 * r + 1) * (2 * r * r + 2 * r + 3) / 3
+    }
+
+    pub fn intersects(&self, other: &ManhattanBall3) -> bool
+    {
+        (self.centre - other.centre).manhatten_size() <= self.radius + other.radius
+    }
+
+    pub fn to_rotated_bounds(self) -> (PointN<4>, PointN<4>)
+    {
+        // An octahedron is the set of points where each of
+        // x+y+z, x+y-z, x-y+z and -x+y+z is within the radius
+        // of the centre's value - i.e. a 4D box
+
+        let c = self.centre;
+        let r = self.radius;
+        let rotated = PointN::new([c.x + c.y + c.z, c.x + c.y - c.z, c.x - c.y + c.z, -c.x + c.y + c.z]);
+
+        (rotated - PointN::new([r; 4]), rotated + PointN::new([r; 4]))
+    }
+
+    pub fn slice_at_z(&self, z: i64) -> Option<ManhattanBall>
+    {
+        let radius = self.radius - (z - self.centre.z).abs();
+
+        if radius >= 0
+        {
+            Some(ManhattanBall::new(Point::new(self.centre.x, self.centre.y), radius))
+        }
+        else
+        {
+            None
+        }
+    }
+
+    pub fn boundary_points(&self) -> impl Iterator<Item = Point3> + '_
+    {
+        ((self.centre.z - self.radius)..=(self.centre.z + self.radius))
+            .flat_map(move |z|
+            {
+                self.slice_at_z(z).unwrap()
+                    .boundary_points()
+                    .map(move |p| Point3::new(p.x, p.y, z))
+            })
+    }
+
+    pub fn num_points_covered_by_at_least(balls: &[ManhattanBall3], k: usize) -> i64
+    {
+        // Sums the 2D counts for each z slice - so the
+        // cost is proportional to the z extent
+
+        let min_z = balls.iter().map(|b| b.centre.z - b.radius).min().unwrap_or(0);
+        let max_z = balls.iter().map(|b| b.centre.z + b.radius).max().unwrap_or(-1);
+
+        (min_z..=max_z)
+            .map(|z|
+            {
+                let slice = balls.iter().filter_map(|b| b.slice_at_z(z)).collect_vec();
+
+                ManhattanBall::num_points_covered_by_at_least(&slice, k)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use std::collections::HashMap;
+
+    fn brute_force_counts(balls: &[ManhattanBall]) -> HashMap<Point, usize>
+    {
+        let mut counts = HashMap::new();
+
+        for b in balls
+        {
+            for y in (b.centre.y - b.radius)..=(b.centre.y + b.radius)
+            {
+                let range = b.x_extent_at_y(y).unwrap();
+
+                for x in range.start..=range.end
+                {
+                    *counts.entry(Point::new(x, y)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        counts
+    }
+
+    #[test]
+    fn test_manhattan_ball()
+    {
+        let ball = ManhattanBall::new_from_edge(Point::new(2, 3), Point::new(4, 4));
+
+        assert_eq!(ball.radius, 3);
+        assert_eq!(ball.num_points(), 25);
+        assert!(ball.contains_point(Point::new(-1, 3)));
+        assert!(!ball.contains_point(Point::new(-1, 4)));
+        assert_eq!(ball.x_extent_at_y(5), Some(RangeInc::new_range(1, 3)));
+        assert_eq!(ball.x_extent_at_y(7), None);
+
+        let boundary = ball.boundary_points().collect_vec();
+        assert_eq!(boundary.len(), 12);
+        assert_eq!(boundary.iter().unique().count(), 12);
+        assert!(boundary.iter().all(|p| (*p - ball.centre).manhatten_size() == 3));
+        assert_eq!(ManhattanBall::new(Point::new(1, 1), 0).boundary_points().collect_vec(), vec![Point::new(1, 1)]);
+
+        let p = Point::new(-3, 8);
+        assert_eq!(ManhattanBall::unrotate_point(ManhattanBall::rotate_point(p)), Some(p));
+        assert_eq!(ManhattanBall::unrotate_point(Point::new(1, 2)), None);
+
+        let other = ManhattanBall::new(Point::new(8, 3), 3);
+        assert!(ball.intersects(&other));
+        assert_eq!(ball.intersection(&other).map(|r| r.num_points()), Some(1));
+        assert!(!ball.intersects(&ManhattanBall::new(Point::new(9, 3), 3)));
+    }
+
+    #[test]
+    fn test_manhattan_ball_coverage()
+    {
+        let balls = vec![
+            ManhattanBall::new(Point::new(0, 0), 4),
+            ManhattanBall::new(Point::new(3, 1), 2),
+            ManhattanBall::new(Point::new(-2, -3), 3),
+            ManhattanBall::new(Point::new(1, 1), 0),
+            ManhattanBall::new(Point::new(10, 10), 1),
+        ];
+
+        let counts = brute_force_counts(&balls);
+
+        for k in 1..=4
+        {
+            assert_eq!(
+                ManhattanBall::num_points_covered_by_at_least(&balls, k),
+                counts.values().filter(|&&c| c >= k).count() as i64);
+        }
+
+        assert_eq!(ManhattanBall::union_num_points(&balls), counts.len() as i64);
+        assert_eq!(ManhattanBall::union_num_points(&[]), 0);
+    }
+
+    #[test]
+    fn test_manhattan_ball_3d()
+    {
+        let ball = ManhattanBall3::new(Point3::new(1, 2, 3), 3);
+
+        let points = PointN::points_in_box(PointN::new([-5, -5, -5]), PointN::new([8, 8, 8]))
+            .map(Point3::from)
+            .filter(|p| ball.contains_point(*p))
+            .collect_vec();
+
+        assert_eq!(ball.num_points(), points.len() as i64);
+        assert_eq!(ball.boundary_points().count(), points.iter().filter(|p| (**p - ball.centre).manhatten_size() == 3).count());
+
+        let (min, max) = ball.to_rotated_bounds();
+        for p in points.iter()
+        {
+            let r = PointN::new([p.x + p.y + p.z, p.x + p.y - p.z, p.x - p.y + p.z, -p.x + p.y + p.z]);
+            assert!((0..4).all(|i| (r[i] >= min[i]) && (r[i] <= max[i])));
+        }
+
+        let other = ManhattanBall3::new(Point3::new(3, 2, 5), 2);
+        assert!(ball.intersects(&other));
+
+        let both = points.iter().filter(|p| other.contains_point(**p)).count() as i64;
+        assert_eq!(ManhattanBall3::num_points_covered_by_at_least(&[ball, other], 2), both);
+        assert_eq!(ManhattanBall3::num_points_covered_by_at_least(&[ball, other], 1), ball.num_points() + other.num_points() - both);
+    }
+}
@@ -1,18 +1,38 @@
+pub mod cube_net;
 pub mod cuboid;
+pub mod cuboid_set;
 pub mod gridarea;
+pub mod hexpoint;
 pub mod line;
 pub mod line_eq;
+pub mod manhattan_ball;
 pub mod point;
+pub mod point_cloud;
 pub mod point3;
+pub mod pointn;
+pub mod polygon;
 pub mod rect;
+pub mod rect_set;
+pub mod transform2;
 pub mod transform3;
 
+pub use cube_net::CubeNet;
 pub use cuboid::Cuboid;
+pub use cuboid_set::CuboidSet;
 pub use gridarea::GridArea;
+pub use hexpoint::{HexOrientation, HexPoint};
 pub use line::Line;
 #[allow(unused)]
 pub use line_eq::LineEquation;
+pub use manhattan_ball::ManhattanBall;
+#[allow(unused)]
+pub use manhattan_ball::ManhattanBall3;
 pub use point::Point;
+pub use point_cloud::PointCloud;
 pub use point3::Point3;
+pub use pointn::PointN;
+pub use polygon::Polygon;
 pub use rect::Rect;
+pub use rect_set::RectSet;
+pub use transform2::Transform2;
 pub use transform3::Transform3;
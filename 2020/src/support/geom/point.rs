@@ -1,3 +1,5 @@
+use crate::support::geom::pointn::PointN;
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Point
 {
@@ -26,7 +28,13 @@ impl Point
 
     pub fn manhatten_size(&self) -> i64
     {
-        self.x.abs() + self.y.abs()
+        PointN::from(*self).manhatten_size()
+    }
+
+    #[allow(dead_code)]
+    pub fn chebyshev_size(&self) -> i64
+    {
+        PointN::from(*self).chebyshev_size()
     }
 
     pub fn directions_4() -> impl Iterator<Item = Point>
@@ -62,11 +70,10 @@ impl Point
 
     pub fn neighbours_8(&self) -> impl Iterator<Item = Point>
     {
-        let px = self.x.clone();
-        let py = self.y.clone();
+        let offset = *self;
 
         Self::directions_8().into_iter()
-            .map(move |d| Point::new(px + d.x, py + d.y))
+            .map(move |d| offset + d)
     }
 
     pub fn rotate_90_left(&self) -> Point
@@ -132,7 +139,7 @@ impl std::ops::Add for Point
     
     fn add(self, rhs: Point) -> Point
     {
-        Point::new(self.x + rhs.x, self.y + rhs.y)
+        Point::from(PointN::from(self) + PointN::from(rhs))
     }
 }
 
@@ -140,8 +147,7 @@ impl std::ops::AddAssign for Point
 {
     fn add_assign(&mut self, rhs: Point)
     {
-        self.x += rhs.x;
-        self.y += rhs.y;
+        *self = *self + rhs;
     }
 }
 
@@ -151,7 +157,7 @@ impl std::ops::Sub for Point
     
     fn sub(self, rhs: Point) -> Point
     {
-        Point::new(self.x - rhs.x, self.y - rhs.y)
+        Point::from(PointN::from(self) - PointN::from(rhs))
     }
 }
 
@@ -159,8 +165,7 @@ impl std::ops::SubAssign for Point
 {
     fn sub_assign(&mut self, rhs: Point)
     {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
+        *self = *self - rhs;
     }
 }
 
@@ -170,7 +175,7 @@ impl std::ops::Mul<i64> for Point
     
     fn mul(self, rhs: i64) -> Point
     {
-        Point::new(self.x * rhs, self.y * rhs)
+        Point::from(PointN::from(self) * rhs)
     }
 }
 
@@ -180,7 +185,7 @@ impl std::ops::Mul<Point> for i64
     
     fn mul(self, rhs: Point) -> Point
     {
-        Point::new(self * rhs.x, self * rhs.y)
+        rhs * self
     }
 }
 
@@ -1,3 +1,5 @@
+use crate::support::geom::pointn::PointN;
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point3
 {
@@ -15,22 +17,18 @@ impl Point3
 
     pub fn manhatten_size(&self) -> i64
     {
-        self.x.abs() + self.y.abs() + self.z.abs()
+        PointN::from(*self).manhatten_size()
     }
 
-    pub fn neighbours_6(&self) -> impl Iterator<Item = Point3>
+    #[allow(dead_code)]
+    pub fn chebyshev_size(&self) -> i64
     {
-        static NEIGHBOURS: [Point3;6] = [
-            Point3{ x: -1, y: 0, z: 0 },
-            Point3{ x: 1, y: 0, z: 0 },
-            Point3{ x: 0, y: -1, z: 0 },
-            Point3{ x: 0, y: 1, z: 0 },
-            Point3{ x: 0, y: 0, z: -1 },
-            Point3{ x: 0, y: 0, z: 1 },
-        ];
+        PointN::from(*self).chebyshev_size()
+    }
 
-        let offset = self.clone();
-        NEIGHBOURS.iter().map(move |p| offset + *p)
+    pub fn neighbours_6(&self) -> impl Iterator<Item = Point3>
+    {
+        PointN::from(*self).neighbours_orthogonal().map(Point3::from)
     }
 }
 
@@ -48,7 +46,7 @@ impl std::ops::Add for Point3
     
     fn add(self, rhs: Point3) -> Point3
     {
-        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+        Point3::from(PointN::from(self) + PointN::from(rhs))
     }
 }
 
@@ -56,9 +54,7 @@ impl std::ops::AddAssign for Point3
 {
     fn add_assign(&mut self, rhs: Point3)
     {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z += rhs.z;
+        *self = *self + rhs;
     }
 }
 
@@ -68,7 +64,7 @@ impl std::ops::Sub for Point3
     
     fn sub(self, rhs: Point3) -> Point3
     {
-        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+        Point3::from(PointN::from(self) - PointN::from(rhs))
     }
 }
 
@@ -76,9 +72,6 @@ impl std::ops::SubAssign for Point3
 {
     fn sub_assign(&mut self, rhs: Point3)
     {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-        self.z -= rhs.z;
+        *self = *self - rhs;
     }
 }
-
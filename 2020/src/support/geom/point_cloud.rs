@@ -0,0 +1,219 @@
+use crate::support::geom::{Point3, Transform3};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(Clone, Debug)]
+pub struct PointCloud
+{
+    // The fingerprint of each pair of points is the
+    // sorted absolute differences along each axis -
+    // these don't change under any of the 24 rotations,
+    // so two clouds can only overlap by N points if
+    // they share at least N * (N - 1) / 2 fingerprints.
+
+    points: Vec<Point3>,
+    fingerprints: HashMap<[i64; 3], Vec<(usize, usize)>>,
+}
+
+fn fingerprint(a: Point3, b: Point3) -> [i64; 3]
+{
+    let d = b - a;
+    let mut result = [d.x.abs(), d.y.abs(), d.z.abs()];
+    result.sort_unstable();
+    result
+}
+
+impl PointCloud
+{
+    pub fn new(points: Vec<Point3>) -> Self
+    {
+        let mut fingerprints: HashMap<[i64; 3], Vec<(usize, usize)>> = HashMap::new();
+
+        for i in 0..points.len()
+        {
+            for j in (i + 1)..points.len()
+            {
+                fingerprints.entry(fingerprint(points[i], points[j]))
+                    .or_default()
+                    .push((i, j));
+            }
+        }
+
+        PointCloud { points, fingerprints }
+    }
+
+    #[allow(dead_code)]
+    pub fn points(&self) -> &[Point3]
+    {
+        &self.points
+    }
+
+    pub fn num_shared_fingerprints(&self, other: &PointCloud) -> usize
+    {
+        self.fingerprints.iter()
+            .filter_map(|(f, pairs)| other.fingerprints.get(f).map(|o| pairs.len().min(o.len())))
+            .sum()
+    }
+
+    pub fn find_alignment(&self, other: &PointCloud, min_overlap: usize) -> Option<Transform3>
+    {
+        // Returns the transform that moves this cloud's
+        // points onto at least min_overlap of the other
+        // cloud's points. Each pair with a matching
+        // fingerprint suggests rotations and offsets.
+
+        if min_overlap < 2
+            || self.num_shared_fingerprints(other) < min_overlap * (min_overlap - 1) / 2
+        {
+            return None;
+        }
+
+        let targets = other.points.iter().copied().collect::<HashSet<_>>();
+
+        for (f, pairs) in self.fingerprints.iter()
+        {
+            let other_pairs = match other.fingerprints.get(f)
+            {
+                Some(op) => op,
+                None => continue,
+            };
+
+            for &(a1, a2) in pairs.iter()
+            {
+                let (pa1, pa2) = (self.points[a1], self.points[a2]);
+
+                for &(b1, b2) in other_pairs.iter()
+                {
+                    let (pb1, pb2) = (other.points[b1], other.points[b2]);
+
+                    for rot in Transform3::rotations_and_reflections_24()
+                    {
+                        let ra1 = rot.transform_point(pa1);
+                        let ra2 = rot.transform_point(pa2);
+
+                        // The pair may line up either way round
+
+                        for (t1, t2) in [(pb1, pb2), (pb2, pb1)]
+                        {
+                            if (ra2 - ra1) != (t2 - t1)
+                            {
+                                continue;
+                            }
+
+                            let transform = rot.append(&Transform3::translation(t1 - ra1));
+
+                            let overlap = self.points.iter()
+                                .filter(|p| targets.contains(&transform.transform_point(**p)))
+                                .count();
+
+                            if overlap >= min_overlap
+                            {
+                                return Some(transform);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn align_all(clouds: &[PointCloud], min_overlap: usize) -> Option<Vec<Transform3>>
+    {
+        // Returns the transform that moves each cloud into
+        // the frame of the first cloud. Clouds are aligned
+        // to any already placed neighbour, working outwards
+        // through the graph of clouds that could overlap.
+
+        let threshold = min_overlap * min_overlap.saturating_sub(1) / 2;
+
+        let neighbours = (0..clouds.len())
+            .map(|i| (0..clouds.len())
+                .filter(|&j| (i != j) && (clouds[i].num_shared_fingerprints(&clouds[j]) >= threshold))
+                .collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut transforms: Vec<Option<Transform3>> = vec![None; clouds.len()];
+        let mut queue = VecDeque::new();
+
+        if !clouds.is_empty()
+        {
+            transforms[0] = Some(Transform3::identity());
+            queue.push_back(0);
+        }
+
+        while let Some(placed) = queue.pop_front()
+        {
+            for &next in neighbours[placed].iter()
+            {
+                if transforms[next].is_some()
+                {
+                    continue;
+                }
+
+                if let Some(t) = clouds[next].find_alignment(&clouds[placed], min_overlap)
+                {
+                    let to_first = t.append(transforms[placed].as_ref().unwrap());
+
+                    transforms[next] = Some(to_first);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        transforms.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_point_cloud_alignment()
+    {
+        let points = vec![
+            Point3::new(0, 0, 0), Point3::new(5, 1, -3), Point3::new(-2, 7, 4),
+            Point3::new(9, -4, 2), Point3::new(3, 3, 8), Point3::new(-6, -1, -5),
+            Point3::new(12, 6, 1),
+        ];
+
+        let t = Transform3::rotations_and_reflections_24().nth(13).unwrap()
+            .append(&Transform3::translation(Point3::new(100, -20, 7)));
+
+        // The second cloud sees the last 5 points plus
+        // some of its own, in its own frame
+
+        let mut moved = points[2..].iter().map(|p| t.transform_point(*p)).collect::<Vec<_>>();
+        moved.push(Point3::new(50, 50, 50));
+        moved.push(Point3::new(-50, 51, 52));
+
+        let a = PointCloud::new(points.clone());
+        let b = PointCloud::new(moved);
+
+        let found = a.find_alignment(&b, 5).unwrap();
+        for p in points[2..].iter()
+        {
+            assert_eq!(found.transform_point(*p), t.transform_point(*p));
+        }
+
+        assert!(a.find_alignment(&b, 6).is_none());
+
+        // Chained through the second cloud
+
+        let c = PointCloud::new(vec![
+            Point3::new(50, 50, 50), Point3::new(-50, 51, 52),
+            t.transform_point(points[5]), t.transform_point(points[6]),
+            Point3::new(0, 1, 2)]);
+
+        let all = PointCloud::align_all(&[a, b, c.clone()], 4).unwrap();
+        assert_eq!(all.len(), 3);
+
+        for p in c.points()
+        {
+            let in_b = all[1].transform_point(*p);
+            assert_eq!(all[2].transform_point(*p), in_b);
+        }
+    }
+}
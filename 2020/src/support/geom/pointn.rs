@@ -0,0 +1,305 @@
+use crate::support::geom::point::Point;
+use crate::support::geom::point3::Point3;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointN<const N: usize>
+{
+    pub coords: [i64; N],
+}
+
+impl<const N: usize> PointN<N>
+{
+    pub fn new(coords: [i64; N]) -> Self
+    {
+        PointN { coords }
+    }
+
+    pub fn zero() -> Self
+    {
+        PointN { coords: [0; N] }
+    }
+
+    pub fn manhatten_size(&self) -> i64
+    {
+        self.coords.iter().map(|c| c.abs()).sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn chebyshev_size(&self) -> i64
+    {
+        self.coords.iter().map(|c| c.abs()).max().unwrap_or(0)
+    }
+
+    pub fn directions_orthogonal() -> impl Iterator<Item = Self>
+    {
+        // The 2N unit steps along each axis, in
+        // axis order, negative before positive
+
+        (0..N).flat_map(|axis|
+        {
+            vec![-1, 1].into_iter().map(move |d|
+            {
+                let mut coords = [0; N];
+                coords[axis] = d;
+                PointN { coords }
+            })
+        })
+    }
+
+    pub fn neighbours_orthogonal(&self) -> impl Iterator<Item = Self>
+    {
+        let offset = *self;
+        Self::directions_orthogonal().map(move |d| offset + d)
+    }
+
+    pub fn directions_all() -> impl Iterator<Item = Self>
+    {
+        // All 3^N - 1 points with every co-ordinate
+        // in -1..=1, excluding the origin
+
+        Self::points_in_box(PointN { coords: [-1; N] }, PointN { coords: [1; N] })
+            .filter(|p| p.coords.iter().any(|&c| c != 0))
+    }
+
+    pub fn neighbours_all(&self) -> impl Iterator<Item = Self>
+    {
+        let offset = *self;
+        Self::directions_all().map(move |d| offset + d)
+    }
+
+    pub fn bounding_box<I>(points: I) -> Option<(Self, Self)>
+        where I: IntoIterator<Item = Self>
+    {
+        // Returns the (inclusive) min and max corners
+
+        points.into_iter()
+            .fold(None, |acc, p|
+            {
+                match acc
+                {
+                    None => Some((p, p)),
+                    Some((mut min, mut max)) =>
+                    {
+                        for i in 0..N
+                        {
+                            min.coords[i] = min.coords[i].min(p.coords[i]);
+                            max.coords[i] = max.coords[i].max(p.coords[i]);
+                        }
+                        Some((min, max))
+                    },
+                }
+            })
+    }
+
+    pub fn points_in_box(min: Self, max: Self) -> impl Iterator<Item = Self>
+    {
+        // Iterates every point in the (inclusive) box,
+        // with the first co-ordinate changing fastest
+
+        let count = (0..N)
+            .map(|i| (max.coords[i] - min.coords[i] + 1).max(0) as usize)
+            .product::<usize>();
+
+        (0..count).map(move |mut index|
+        {
+            let mut coords = min.coords;
+
+            for (i, c) in coords.iter_mut().enumerate()
+            {
+                let size = (max.coords[i] - min.coords[i] + 1) as usize;
+                *c += (index % size) as i64;
+                index /= size;
+            }
+
+            PointN { coords }
+        })
+    }
+}
+
+impl<const N: usize> std::fmt::Debug for PointN<N>
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        let coords = self.coords.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+
+        fmt.write_fmt(format_args!("({})", coords.join(", ")))
+    }
+}
+
+impl<const N: usize> std::ops::Index<usize> for PointN<N>
+{
+    type Output = i64;
+
+    fn index(&self, index: usize) -> &i64
+    {
+        &self.coords[index]
+    }
+}
+
+impl<const N: usize> std::ops::IndexMut<usize> for PointN<N>
+{
+    fn index_mut(&mut self, index: usize) -> &mut i64
+    {
+        &mut self.coords[index]
+    }
+}
+
+impl<const N: usize> std::ops::Add for PointN<N>
+{
+    type Output = PointN<N>;
+
+    fn add(mut self, rhs: PointN<N>) -> PointN<N>
+    {
+        self += rhs;
+        self
+    }
+}
+
+impl<const N: usize> std::ops::AddAssign for PointN<N>
+{
+    fn add_assign(&mut self, rhs: PointN<N>)
+    {
+        for i in 0..N
+        {
+            self.coords[i] += rhs.coords[i];
+        }
+    }
+}
+
+impl<const N: usize> std::ops::Sub for PointN<N>
+{
+    type Output = PointN<N>;
+
+    fn sub(mut self, rhs: PointN<N>) -> PointN<N>
+    {
+        self -= rhs;
+        self
+    }
+}
+
+impl<const N: usize> std::ops::SubAssign for PointN<N>
+{
+    fn sub_assign(&mut self, rhs: PointN<N>)
+    {
+        for i in 0..N
+        {
+            self.coords[i] -= rhs.coords[i];
+        }
+    }
+}
+
+impl<const N: usize> std::ops::Neg for PointN<N>
+{
+    type Output = PointN<N>;
+
+    fn neg(self) -> PointN<N>
+    {
+        PointN { coords: self.coords.map(|c| -c) }
+    }
+}
+
+impl<const N: usize> std::ops::Mul<i64> for PointN<N>
+{
+    type Output = PointN<N>;
+
+    fn mul(self, rhs: i64) -> PointN<N>
+    {
+        PointN { coords: self.coords.map(|c| c * rhs) }
+    }
+}
+
+impl From<Point> for PointN<2>
+{
+    fn from(p: Point) -> Self
+    {
+        PointN::new([p.x, p.y])
+    }
+}
+
+impl From<PointN<2>> for Point
+{
+    fn from(p: PointN<2>) -> Self
+    {
+        Point::new(p.coords[0], p.coords[1])
+    }
+}
+
+impl From<Point3> for PointN<3>
+{
+    fn from(p: Point3) -> Self
+    {
+        PointN::new([p.x, p.y, p.z])
+    }
+}
+
+impl From<PointN<3>> for Point3
+{
+    fn from(p: PointN<3>) -> Self
+    {
+        Point3::new(p.coords[0], p.coords[1], p.coords[2])
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_point_n_ops()
+    {
+        let a = PointN::new([1, -2, 3, 0]);
+        let b = PointN::new([-4, 5, 0, 2]);
+
+        assert_eq!(a + b, PointN::new([-3, 3, 3, 2]));
+        assert_eq!(a - b, PointN::new([5, -7, 3, -2]));
+        assert_eq!(-a, PointN::new([-1, 2, -3, 0]));
+        assert_eq!(a * 3, PointN::new([3, -6, 9, 0]));
+        assert_eq!(a[1], -2);
+        assert_eq!(a.manhatten_size(), 6);
+        assert_eq!(b.chebyshev_size(), 5);
+        assert_eq!(format!("{:?}", a), "(1, -2, 3, 0)");
+
+        assert_eq!(Point::from(PointN::from(Point::new(7, -8))), Point::new(7, -8));
+        assert_eq!(PointN::from(Point3::new(1, 2, 3)), PointN::new([1, 2, 3]));
+
+        assert_eq!(Point::new(3, -7) - Point::new(1, 1) * 2, Point::new(1, -9));
+        assert_eq!(Point::new(3, -7).chebyshev_size(), 7);
+        assert_eq!(Point::new(5, 5).neighbours_8().collect::<Vec<_>>(), Point::directions_8().into_iter().map(|d| Point::new(5, 5) + d).collect::<Vec<_>>());
+        assert_eq!(Point3::new(1, 2, 3) + Point3::new(-1, 0, 4), Point3::new(0, 2, 7));
+        assert_eq!(Point3::new(1, -5, 3).chebyshev_size(), 5);
+    }
+
+    #[test]
+    fn test_point_n_neighbours()
+    {
+        assert_eq!(PointN::<2>::directions_orthogonal().count(), 4);
+        assert_eq!(PointN::<3>::directions_orthogonal().count(), 6);
+        assert_eq!(PointN::<4>::directions_orthogonal().count(), 8);
+
+        assert_eq!(PointN::<2>::directions_all().count(), 8);
+        assert_eq!(PointN::<3>::directions_all().count(), 26);
+        assert_eq!(PointN::<4>::directions_all().count(), 80);
+
+        let p = PointN::new([10, 20]);
+        assert!(p.neighbours_orthogonal().all(|n| (n - p).manhatten_size() == 1));
+        assert!(p.neighbours_all().all(|n| (n - p).chebyshev_size() == 1));
+        assert!(!p.neighbours_all().any(|n| n == p));
+    }
+
+    #[test]
+    fn test_point_n_bounding_box()
+    {
+        let points = vec![PointN::new([1, 5, -1]), PointN::new([-2, 3, 0]), PointN::new([0, 4, 2])];
+
+        let (min, max) = PointN::bounding_box(points).unwrap();
+        assert_eq!(min, PointN::new([-2, 3, -1]));
+        assert_eq!(max, PointN::new([1, 5, 2]));
+        assert_eq!(PointN::points_in_box(min, max).count(), 4 * 3 * 4);
+
+        assert_eq!(PointN::<3>::bounding_box(Vec::new()), None);
+        assert_eq!(
+            PointN::points_in_box(PointN::new([0, 0]), PointN::new([1, 1])).collect::<Vec<_>>(),
+            vec![PointN::new([0, 0]), PointN::new([1, 0]), PointN::new([0, 1]), PointN::new([1, 1])]);
+    }
+}
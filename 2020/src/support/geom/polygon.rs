@@ -0,0 +1,244 @@
+use crate::support::geom::line::Line;
+use crate::support::geom::point::Point;
+use crate::support::geom::rect::Rect;
+use crate::support::num::gcd;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polygon
+{
+    // A simple polygon with lattice point vertices.
+    // The chain is implicitly closed - the last vertex
+    // connects back to the first.
+
+    vertices: Vec<Point>,
+}
+
+impl Polygon
+{
+    pub fn new_from_points(points: Vec<Point>) -> Self
+    {
+        let mut vertices = points;
+
+        assert!(!vertices.is_empty());
+
+        if (vertices.len() > 1) && (vertices.first() == vertices.last())
+        {
+            vertices.pop();
+        }
+
+        Polygon { vertices }
+    }
+
+    pub fn new_from_lines(lines: &[Line]) -> Self
+    {
+        // The lines must be rectilinear and form
+        // a closed chain, each starting where the
+        // previous one ended
+
+        assert!(!lines.is_empty());
+
+        for (i, line) in lines.iter().enumerate()
+        {
+            let next = &lines[(i + 1) % lines.len()];
+
+            assert!((line.start.x == line.end.x) || (line.start.y == line.end.y));
+            assert!(line.end == next.start);
+        }
+
+        Polygon::new_from_points(lines.iter().map(|l| l.start).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn vertices(&self) -> &[Point]
+    {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = Line> + '_
+    {
+        (0..self.vertices.len())
+            .map(move |i| Line::new(self.vertices[i], self.vertices[(i + 1) % self.vertices.len()]))
+    }
+
+    #[allow(dead_code)]
+    pub fn get_bounds(&self) -> Rect
+    {
+        let min_x = self.vertices.iter().map(|p| p.x).min().unwrap();
+        let max_x = self.vertices.iter().map(|p| p.x).max().unwrap();
+        let min_y = self.vertices.iter().map(|p| p.y).min().unwrap();
+        let max_y = self.vertices.iter().map(|p| p.y).max().unwrap();
+
+        Rect::new_from_coords(min_x, min_y, max_x, max_y)
+    }
+
+    pub fn double_area(&self) -> i64
+    {
+        // Shoelace formula - twice the area is always
+        // an exact integer for lattice polygons
+
+        self.edges()
+            .map(|e| (e.start.x * e.end.y) - (e.end.x * e.start.y))
+            .sum::<i64>()
+            .abs()
+    }
+
+    #[allow(dead_code)]
+    pub fn area(&self) -> f64
+    {
+        self.double_area() as f64 / 2.0
+    }
+
+    pub fn num_boundary_points(&self) -> i64
+    {
+        self.edges()
+            .map(|e| gcd((e.end.x - e.start.x).unsigned_abs(), (e.end.y - e.start.y).unsigned_abs()) as i64)
+            .sum()
+    }
+
+    pub fn num_interior_points(&self) -> i64
+    {
+        // Pick's theorem: A = I + B/2 - 1
+
+        (self.double_area() - self.num_boundary_points() + 2) / 2
+    }
+
+    pub fn num_enclosed_points(&self) -> i64
+    {
+        self.num_interior_points() + self.num_boundary_points()
+    }
+
+    pub fn is_point_on_boundary(&self, point: &Point) -> bool
+    {
+        self.edges().any(|e|
+        {
+            let cross = ((e.end.x - e.start.x) * (point.y - e.start.y))
+                - ((e.end.y - e.start.y) * (point.x - e.start.x));
+
+            (cross == 0)
+                && (point.x >= e.start.x.min(e.end.x))
+                && (point.x <= e.start.x.max(e.end.x))
+                && (point.y >= e.start.y.min(e.end.y))
+                && (point.y <= e.start.y.max(e.end.y))
+        })
+    }
+
+    pub fn is_point_inside(&self, point: &Point) -> bool
+    {
+        // Strictly inside - points on the boundary are not included.
+        // Casts a ray in the +x direction and counts the crossings,
+        // using exact integer maths to find which side of each
+        // edge the point is on.
+
+        if self.is_point_on_boundary(point)
+        {
+            return false;
+        }
+
+        let mut inside = false;
+
+        for e in self.edges()
+        {
+            if (e.start.y > point.y) != (e.end.y > point.y)
+            {
+                let cross = ((e.end.x - e.start.x) * (point.y - e.start.y))
+                    - ((e.end.y - e.start.y) * (point.x - e.start.x));
+
+                if (cross > 0) == (e.end.y > e.start.y)
+                {
+                    inside = !inside;
+                }
+            }
+        }
+
+        inside
+    }
+
+    #[allow(dead_code)]
+    pub fn interior_points(&self) -> impl Iterator<Item = Point> + '_
+    {
+        // Scans the bounding rect - so only
+        // suitable for small polygons
+
+        let bounds = self.get_bounds();
+
+        (bounds.get_min_y()..=bounds.get_max_y())
+            .flat_map(move |y| (bounds.get_min_x()..=bounds.get_max_x()).map(move |x| Point::new(x, y)))
+            .filter(move |p| self.is_point_inside(p))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_polygon_square()
+    {
+        let square = Polygon::new_from_points(vec![
+            Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4), Point::new(0, 0)]);
+
+        assert_eq!(square.vertices().len(), 4);
+        assert_eq!(square.double_area(), 32);
+        assert_eq!(square.area(), 16.0);
+        assert_eq!(square.num_boundary_points(), 16);
+        assert_eq!(square.num_interior_points(), 9);
+        assert_eq!(square.num_enclosed_points(), 25);
+        assert_eq!(square.interior_points().count(), 9);
+
+        assert!(square.is_point_inside(&Point::new(1, 3)));
+        assert!(!square.is_point_inside(&Point::new(4, 2)));
+        assert!(square.is_point_on_boundary(&Point::new(4, 2)));
+        assert!(!square.is_point_inside(&Point::new(5, 2)));
+        assert!(!square.is_point_on_boundary(&Point::new(5, 2)));
+    }
+
+    #[test]
+    fn test_polygon_triangle()
+    {
+        // Odd double-area, and a diagonal edge
+        // with lattice points part way along
+
+        let triangle = Polygon::new_from_points(vec![
+            Point::new(0, 0), Point::new(5, 0), Point::new(0, 3)]);
+
+        assert_eq!(triangle.double_area(), 15);
+        assert_eq!(triangle.area(), 7.5);
+        assert_eq!(triangle.num_boundary_points(), 9);
+        assert_eq!(triangle.num_interior_points(), 4);
+        assert_eq!(triangle.interior_points().collect::<Vec<_>>(), vec![
+            Point::new(1, 1), Point::new(2, 1), Point::new(3, 1), Point::new(1, 2)]);
+
+        let diagonal = Polygon::new_from_points(vec![
+            Point::new(0, 0), Point::new(6, 0), Point::new(0, 4)]);
+
+        assert!(diagonal.is_point_on_boundary(&Point::new(3, 2)));
+        assert!(!diagonal.is_point_inside(&Point::new(3, 2)));
+        assert!(diagonal.is_point_inside(&Point::new(2, 2)));
+    }
+
+    #[test]
+    fn test_polygon_from_lines()
+    {
+        // An L-shape, anti-clockwise
+
+        let points = [
+            Point::new(0, 0), Point::new(0, 6), Point::new(6, 6),
+            Point::new(6, 4), Point::new(2, 4), Point::new(2, 0)];
+
+        let lines = (0..points.len())
+            .map(|i| Line::new(points[i], points[(i + 1) % points.len()]))
+            .collect::<Vec<_>>();
+
+        let polygon = Polygon::new_from_lines(&lines);
+
+        assert_eq!(polygon.double_area(), 2 * 20);
+        assert_eq!(polygon.num_boundary_points(), 24);
+        assert_eq!(polygon.num_interior_points(), 9);
+        assert_eq!(polygon.interior_points().count(), 9);
+        assert_eq!(polygon.get_bounds(), Rect::new_from_coords(0, 0, 6, 6));
+
+        assert!(polygon.is_point_inside(&Point::new(1, 5)));
+        assert!(!polygon.is_point_inside(&Point::new(4, 2)));
+    }
+}
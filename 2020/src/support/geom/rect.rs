@@ -100,6 +100,44 @@ impl Rect
     {
         p.x >= self.min_x && p.x <= self.max_x && p.y >= self.min_y && p.y <= self.max_y
     }
+
+    pub fn num_points(&self) -> i64
+    {
+        // Unlike area(), this counts the (inclusive)
+        // lattice points covered by the rect
+
+        (self.max_x - self.min_x + 1) * (self.max_y - self.min_y + 1)
+    }
+
+    pub fn difference(&self, other: Rect) -> Vec<Rect>
+    {
+        // Up to four non-overlapping pieces - the full-height
+        // left and right strips, then the top and bottom strips
+        // between them
+
+        let intersection = match self.intersection(other)
+        {
+            None => return vec![*self],
+            Some(i) => i,
+        };
+
+        let mut result = Vec::with_capacity(4);
+
+        let mut add_possibility = |min_x: i64, min_y: i64, max_x: i64, max_y: i64|
+        {
+            if (min_x <= max_x) && (min_y <= max_y)
+            {
+                result.push(Rect { min_x, min_y, max_x, max_y });
+            }
+        };
+
+        add_possibility(self.min_x, self.min_y, intersection.min_x - 1, self.max_y);
+        add_possibility(intersection.max_x + 1, self.min_y, self.max_x, self.max_y);
+        add_possibility(intersection.min_x, self.min_y, intersection.max_x, intersection.min_y - 1);
+        add_possibility(intersection.min_x, intersection.max_y + 1, intersection.max_x, self.max_y);
+
+        result
+    }
 }
 
 #[cfg(test)]
@@ -113,4 +151,18 @@ mod tests
         assert_eq!(Rect::new_from_coords(0, 0, 0, 0).intersection(Rect::new_from_coords(0, 0, 0, 0)),
             Some(Rect::new_from_coords(0, 0, 0, 0)));
     }
+
+    #[test]
+    fn test_rect_difference()
+    {
+        let rect = Rect::new_from_coords(0, 0, 9, 9);
+
+        assert_eq!(rect.difference(Rect::new_from_coords(20, 20, 30, 30)), vec![rect]);
+        assert_eq!(rect.difference(Rect::new_from_coords(-1, -1, 10, 10)), vec![]);
+        assert_eq!(rect.difference(Rect::new_from_coords(-1, -1, 4, 10)), vec![Rect::new_from_coords(5, 0, 9, 9)]);
+
+        let pieces = rect.difference(Rect::new_from_coords(3, 3, 5, 5));
+        assert_eq!(pieces.len(), 4);
+        assert_eq!(pieces.iter().map(|r| r.num_points()).sum::<i64>(), 100 - 9);
+    }
 }
@@ -0,0 +1,214 @@
+use crate::support::geom::{Point, Rect};
+
+#[derive(Clone, Debug)]
+pub struct RectSet
+{
+    // A set of lattice points, stored as disjoint
+    // (inclusive) rects - the 2D version of CuboidSet
+
+    rects: Vec<Rect>,
+}
+
+impl RectSet
+{
+    pub fn new() -> Self
+    {
+        RectSet { rects: Vec::new() }
+    }
+
+    pub fn new_from_rect(rect: Rect) -> Self
+    {
+        RectSet { rects: vec![rect] }
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.rects.is_empty()
+    }
+
+    pub fn area(&self) -> i64
+    {
+        // The number of points covered
+
+        self.rects.iter().map(|r| r.num_points()).sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn contains_point(&self, point: Point) -> bool
+    {
+        self.rects.iter().any(|r| r.does_point_intersect(point))
+    }
+
+    pub fn rects(&self) -> impl Iterator<Item = Rect> + '_
+    {
+        self.rects.iter().copied()
+    }
+
+    pub fn insert_rect(&mut self, rect: Rect)
+    {
+        self.remove_rect(rect);
+        self.rects.push(rect);
+    }
+
+    pub fn insert_set(&mut self, set: &RectSet)
+    {
+        for r in set.rects.iter()
+        {
+            self.insert_rect(*r);
+        }
+    }
+
+    pub fn remove_rect(&mut self, rect: Rect)
+    {
+        self.rects = self.rects.iter()
+            .flat_map(|existing| existing.difference(rect))
+            .collect();
+    }
+
+    #[allow(dead_code)]
+    pub fn remove_set(&mut self, set: &RectSet)
+    {
+        for r in set.rects.iter()
+        {
+            self.remove_rect(*r);
+        }
+    }
+
+    pub fn intersect_with_rect(&mut self, rect: Rect)
+    {
+        self.rects = self.rects.iter()
+            .filter_map(|existing| existing.intersection(rect))
+            .collect();
+    }
+
+    #[allow(dead_code)]
+    pub fn intersection(&self, other: &RectSet) -> RectSet
+    {
+        let rects = self.rects.iter()
+            .flat_map(|a| other.rects.iter().filter_map(move |b| a.intersection(*b)))
+            .collect();
+
+        RectSet { rects }
+    }
+
+    #[allow(dead_code)]
+    pub fn find_uncovered_point(&self, bounds: Rect) -> Option<Point>
+    {
+        // Returns the uncovered point within the bounds
+        // with the lowest y, and then the lowest x
+
+        let mut uncovered = RectSet::new_from_rect(bounds);
+        uncovered.remove_set(self);
+
+        uncovered.rects.iter()
+            .map(|r| Point::new(r.get_min_x(), r.get_min_y()))
+            .min_by_key(|p| (p.y, p.x))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn rect(x1: i64, y1: i64, x2: i64, y2: i64) -> Rect
+    {
+        Rect::new_from_coords(x1, y1, x2, y2)
+    }
+
+    #[test]
+    fn test_rect_set_edges()
+    {
+        let square = rect(0, 0, 4, 4);
+
+        // Touching the edge, but not overlapping it
+
+        let mut set = RectSet::new_from_rect(square);
+        set.remove_rect(rect(5, 0, 9, 4));
+        assert_eq!(set.rects().collect::<Vec<_>>(), vec![square]);
+
+        set.insert_rect(rect(5, 0, 9, 4));
+        assert_eq!(set.area(), 50);
+        assert_eq!(set.rects().count(), 2);
+
+        // Overlapping only the edge column or row
+
+        let mut set = RectSet::new_from_rect(square);
+        set.remove_rect(rect(4, -3, 9, 9));
+        assert_eq!(set.rects().collect::<Vec<_>>(), vec![rect(0, 0, 3, 4)]);
+
+        set.remove_rect(rect(0, 0, 3, 0));
+        assert_eq!(set.rects().collect::<Vec<_>>(), vec![rect(0, 1, 3, 4)]);
+
+        // Meeting at a single corner point
+
+        let mut set = RectSet::new_from_rect(square);
+        set.intersect_with_rect(rect(4, 4, 8, 8));
+        assert_eq!(set.rects().collect::<Vec<_>>(), vec![rect(4, 4, 4, 4)]);
+    }
+
+    #[test]
+    fn test_rect_set_contained()
+    {
+        let square = rect(0, 0, 4, 4);
+
+        // A hole strictly inside leaves four pieces
+
+        let mut set = RectSet::new_from_rect(square);
+        set.remove_rect(rect(1, 1, 3, 3));
+
+        assert_eq!(set.area(), 25 - 9);
+        assert_eq!(set.rects().count(), 4);
+        assert!(!set.contains_point(Point::new(2, 2)));
+        assert!(set.contains_point(Point::new(0, 2)));
+        assert!(set.contains_point(Point::new(4, 4)));
+        assert_eq!(set.find_uncovered_point(square), Some(Point::new(1, 1)));
+
+        // A hole sharing two edges leaves an L
+
+        let mut set = RectSet::new_from_rect(square);
+        set.remove_rect(rect(2, 2, 4, 4));
+        assert_eq!(set.area(), 25 - 9);
+        assert_eq!(set.rects().count(), 2);
+        assert_eq!(set.find_uncovered_point(square), Some(Point::new(2, 2)));
+
+        // Inserting a containing rect replaces everything
+
+        set.insert_rect(rect(-1, -1, 5, 5));
+        assert_eq!(set.rects().collect::<Vec<_>>(), vec![rect(-1, -1, 5, 5)]);
+        assert_eq!(set.intersection(&RectSet::new_from_rect(square)).rects().collect::<Vec<_>>(), vec![square]);
+
+        set.remove_rect(rect(-1, -1, 5, 5));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_rect_set_overlaps()
+    {
+        // Claims from 2018 day 3
+
+        let claims = vec![
+            Rect::new_from_coords(1, 3, 4, 6),
+            Rect::new_from_coords(3, 1, 6, 4),
+            Rect::new_from_coords(5, 5, 6, 6),
+        ];
+
+        let mut covered = RectSet::new();
+        let mut overlaps = RectSet::new();
+
+        for c in claims
+        {
+            let mut common = covered.clone();
+            common.intersect_with_rect(c);
+
+            overlaps.insert_set(&common);
+            covered.insert_rect(c);
+        }
+
+        assert_eq!(covered.area(), 16 + 16 - 4 + 4);
+        assert_eq!(overlaps.area(), 4);
+        assert_eq!(overlaps.rects().collect::<Vec<_>>(), vec![Rect::new_from_coords(3, 3, 4, 4)]);
+        assert_eq!(covered.find_uncovered_point(Rect::new_from_coords(0, 0, 7, 7)), Some(Point::new(0, 0)));
+        assert_eq!(covered.find_uncovered_point(Rect::new_from_coords(3, 3, 4, 4)), None);
+    }
+}
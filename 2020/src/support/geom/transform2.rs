@@ -0,0 +1,248 @@
+use crate::support::chargrid::CharGrid;
+use crate::support::geom::{Point, Rect};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Transform2
+{
+    // A 2D rotation/reflection plus translation,
+    // as a 3x3 matrix acting on (x, y, 1).
+    // Positive y is "down", as in the grids.
+
+    coords: [[i64; 3]; 3],
+}
+
+impl Transform2
+{
+    pub fn identity() -> Transform2
+    {
+        Transform2
+        {
+            coords: [[1, 0, 0], [0, 1, 0], [0, 0, 1]]
+        }
+    }
+
+    pub fn rotations_and_reflections_8() -> impl Iterator<Item = &'static Transform2>
+    {
+        // The 8 elements of D4 - each clockwise rotation,
+        // followed by the same rotation flipped horizontally
+
+        const TRANSFORMS: [Transform2; 8] = [
+
+            Transform2 { coords: [[ 1,  0, 0], [ 0,  1, 0], [0, 0, 1]] },
+            Transform2 { coords: [[-1,  0, 0], [ 0,  1, 0], [0, 0, 1]] },
+
+            Transform2 { coords: [[ 0, -1, 0], [ 1,  0, 0], [0, 0, 1]] },
+            Transform2 { coords: [[ 0,  1, 0], [ 1,  0, 0], [0, 0, 1]] },
+
+            Transform2 { coords: [[-1,  0, 0], [ 0, -1, 0], [0, 0, 1]] },
+            Transform2 { coords: [[ 1,  0, 0], [ 0, -1, 0], [0, 0, 1]] },
+
+            Transform2 { coords: [[ 0,  1, 0], [-1,  0, 0], [0, 0, 1]] },
+            Transform2 { coords: [[ 0, -1, 0], [-1,  0, 0], [0, 0, 1]] },
+        ];
+
+        TRANSFORMS.iter()
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_cw_90() -> Transform2
+    {
+        Transform2
+        {
+            coords: [[0, -1, 0], [1, 0, 0], [0, 0, 1]]
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn flip_horizontally() -> Transform2
+    {
+        Transform2
+        {
+            coords: [[-1, 0, 0], [0, 1, 0], [0, 0, 1]]
+        }
+    }
+
+    pub fn translation(offset: Point) -> Transform2
+    {
+        Transform2
+        {
+            coords: [[1, 0, offset.x], [0, 1, offset.y], [0, 0, 1]]
+        }
+    }
+
+    pub fn append(&self, other: &Transform2) -> Transform2
+    {
+        // Applies self, and then other
+
+        let mut result = Transform2::identity();
+
+        for a in 0..3
+        {
+            for b in 0..3
+            {
+                result.coords[a][b] =
+                    other.coords[a][0] * self.coords[0][b]
+                    + other.coords[a][1] * self.coords[1][b]
+                    + other.coords[a][2] * self.coords[2][b];
+            }
+        }
+
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn inverse(&self) -> Transform2
+    {
+        // The rotation part is orthogonal, so its
+        // inverse is the transpose. The translation
+        // then has to be undone in the rotated frame.
+
+        let c = &self.coords;
+
+        let (a, b, d, e) = (c[0][0], c[1][0], c[0][1], c[1][1]);
+        let tx = -(a * c[0][2] + b * c[1][2]);
+        let ty = -(d * c[0][2] + e * c[1][2]);
+
+        Transform2
+        {
+            coords: [[a, b, tx], [d, e, ty], [0, 0, 1]]
+        }
+    }
+
+    pub fn transform_point(&self, point: Point) -> Point
+    {
+        let x = self.coords[0][0] * point.x
+              + self.coords[0][1] * point.y
+              + self.coords[0][2];
+
+        let y = self.coords[1][0] * point.x
+              + self.coords[1][1] * point.y
+              + self.coords[1][2];
+
+        Point::new(x, y)
+    }
+
+    pub fn transform_rect(&self, rect: Rect) -> Rect
+    {
+        let p1 = self.transform_point(Point::new(rect.get_min_x(), rect.get_min_y()));
+        let p2 = self.transform_point(Point::new(rect.get_max_x(), rect.get_max_y()));
+
+        Rect::new(p1, p2)
+    }
+
+    pub fn fit_to_grid(&self, grid: &CharGrid) -> Transform2
+    {
+        // Adjusts the translation so that the grid's
+        // points are mapped back onto (0, 0) upwards
+
+        let bounds = Rect::new_from_coords(0, 0, grid.get_width() - 1, grid.get_height() - 1);
+        let moved = self.transform_rect(bounds);
+
+        self.append(&Transform2::translation(Point::new(-moved.get_min_x(), -moved.get_min_y())))
+    }
+
+    pub fn transform_grid(&self, grid: &CharGrid) -> CharGrid
+    {
+        // The translation is ignored - the result
+        // always starts at (0, 0)
+
+        let fitted = self.fit_to_grid(grid);
+
+        let (width, height) = if self.coords[0][0] == 0
+        {
+            (grid.get_height(), grid.get_width())
+        }
+        else
+        {
+            (grid.get_width(), grid.get_height())
+        };
+
+        let mut result = CharGrid::new_from_fill(width as usize, height as usize, grid.get_default());
+
+        for p in grid.all_points()
+        {
+            result.put_char(&fitted.transform_point(p), grid.get_char(&p));
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_transform2_group()
+    {
+        let all = Transform2::rotations_and_reflections_8().copied().collect::<Vec<_>>();
+
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 8);
+        assert_eq!(all[0], Transform2::identity());
+        assert_eq!(all[1], Transform2::flip_horizontally());
+        assert_eq!(all[2], Transform2::rotate_cw_90());
+
+        // Closed under composition, and each has
+        // an inverse within the group
+
+        for a in all.iter()
+        {
+            assert!(all.contains(&a.inverse()));
+            assert_eq!(a.append(&a.inverse()), Transform2::identity());
+
+            for b in all.iter()
+            {
+                assert!(all.contains(&a.append(b)));
+            }
+        }
+
+        let rot = Transform2::rotate_cw_90();
+        assert_eq!(rot.append(&rot).append(&rot).append(&rot), Transform2::identity());
+        assert_eq!(rot.transform_point(Point::new(1, 0)), Point::new(0, 1));
+    }
+
+    #[test]
+    fn test_transform2_translation()
+    {
+        let t = Transform2::rotate_cw_90()
+            .append(&Transform2::translation(Point::new(5, -3)))
+            .append(&Transform2::flip_horizontally());
+
+        let p = Point::new(7, 2);
+        assert_eq!(t.transform_point(p), Point::new(-3, 4));
+        assert_eq!(t.inverse().transform_point(t.transform_point(p)), p);
+        assert_eq!(t.append(&t.inverse()), Transform2::identity());
+
+        assert_eq!(
+            t.transform_rect(Rect::new_from_coords(0, 0, 3, 1)),
+            Rect::new_from_coords(-5, -3, -4, 0));
+    }
+
+    #[test]
+    fn test_transform2_grid()
+    {
+        let grid = CharGrid::new_from_input("12\n34\n56", ' ');
+
+        assert_eq!(
+            Transform2::rotate_cw_90().transform_grid(&grid).to_string(),
+            grid.rotate_cw_90().to_string());
+
+        assert_eq!(
+            Transform2::flip_horizontally().transform_grid(&grid).to_string(),
+            grid.flip_horizontally().to_string());
+
+        let orientations = grid.all_orientations();
+        assert_eq!(orientations.len(), 8);
+        assert_eq!(orientations.iter().map(|(_, g)| g.to_string()).collect::<HashSet<_>>().len(), 8);
+
+        for (t, g) in orientations.iter()
+        {
+            for p in grid.all_points()
+            {
+                assert_eq!(g.get_char(&t.transform_point(p)), grid.get_char(&p));
+            }
+        }
+    }
+}
@@ -61,7 +61,7 @@ impl Transform3
     {
         Transform3
         {
-            coords: [[1, 0, 0, offset.x], [0, 1, 0, offset.y], [0, 0, 1, offset.z], [0, 0, 0, 0]]
+            coords: [[1, 0, 0, offset.x], [0, 1, 0, offset.y], [0, 0, 1, offset.z], [0, 0, 0, 1]]
         }
     }
 
@@ -0,0 +1,644 @@
+use num_traits::Zero;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+#[derive(Clone, Debug)]
+pub struct Graph<N, E>
+    where N: Hash + Eq + Clone,
+        E: Clone,
+{
+    // Nodes are stored once, and edges refer to
+    // them by index. An undirected edge is stored
+    // as an edge in each direction.
+
+    directed: bool,
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    edges: Vec<Vec<(usize, E)>>,
+}
+
+impl<N, E> Graph<N, E>
+    where N: Hash + Eq + Clone,
+        E: Clone,
+{
+    pub fn new_directed() -> Self
+    {
+        Graph { directed: true, nodes: Vec::new(), indices: HashMap::new(), edges: Vec::new() }
+    }
+
+    #[allow(dead_code)]
+    pub fn new_undirected() -> Self
+    {
+        Graph { directed: false, nodes: Vec::new(), indices: HashMap::new(), edges: Vec::new() }
+    }
+
+    pub fn add_node(&mut self, node: N) -> usize
+    {
+        if let Some(index) = self.indices.get(&node)
+        {
+            return *index;
+        }
+
+        self.nodes.push(node.clone());
+        self.edges.push(Vec::new());
+        self.indices.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, edge: E)
+    {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+
+        if !self.directed && (from != to)
+        {
+            self.edges[to].push((from, edge.clone()));
+        }
+
+        self.edges[from].push((to, edge));
+    }
+
+    #[allow(dead_code)]
+    pub fn num_nodes(&self) -> usize
+    {
+        self.nodes.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn nodes(&self) -> &[N]
+    {
+        &self.nodes
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours(&self, node: &N) -> impl Iterator<Item = (&N, &E)>
+    {
+        self.indices.get(node)
+            .map(|i| self.edges[*i].as_slice())
+            .unwrap_or(&[])
+            .iter()
+            .map(move |(to, e)| (&self.nodes[*to], e))
+    }
+
+    pub fn reversed(&self) -> Self
+    {
+        let mut edges = vec![Vec::new(); self.nodes.len()];
+
+        for (from, out) in self.edges.iter().enumerate()
+        {
+            for (to, e) in out.iter()
+            {
+                edges[*to].push((from, e.clone()));
+            }
+        }
+
+        Graph { directed: self.directed, nodes: self.nodes.clone(), indices: self.indices.clone(), edges }
+    }
+
+    pub fn topological_sort(&self) -> Result<Vec<N>, Vec<N>>
+    {
+        // Kahn's algorithm. If the graph has a cycle,
+        // returns one of the cycles instead.
+
+        let mut in_degree = vec![0; self.nodes.len()];
+
+        for out in self.edges.iter()
+        {
+            for (to, _) in out.iter()
+            {
+                in_degree[*to] += 1;
+            }
+        }
+
+        let mut queue = (0..self.nodes.len()).filter(|i| in_degree[*i] == 0).collect::<VecDeque<_>>();
+        let mut order = Vec::new();
+
+        while let Some(i) = queue.pop_front()
+        {
+            order.push(i);
+
+            for (to, _) in self.edges[i].iter()
+            {
+                in_degree[*to] -= 1;
+
+                if in_degree[*to] == 0
+                {
+                    queue.push_back(*to);
+                }
+            }
+        }
+
+        if order.len() == self.nodes.len()
+        {
+            return Ok(order.into_iter().map(|i| self.nodes[i].clone()).collect());
+        }
+
+        // Every node left over has an edge in from
+        // another left over node - so walking those
+        // edges backwards must eventually repeat
+
+        let mut into = vec![None; self.nodes.len()];
+
+        for (from, out) in self.edges.iter().enumerate()
+        {
+            for (to, _) in out.iter()
+            {
+                if in_degree[from] > 0
+                {
+                    into[*to] = Some(from);
+                }
+            }
+        }
+
+        let mut pos = (0..self.nodes.len()).find(|i| in_degree[*i] > 0).unwrap();
+        let mut visited = vec![false; self.nodes.len()];
+
+        while !visited[pos]
+        {
+            visited[pos] = true;
+            pos = into[pos].unwrap();
+        }
+
+        let mut cycle = vec![pos];
+        let mut next = into[pos].unwrap();
+
+        while next != pos
+        {
+            cycle.push(next);
+            next = into[next].unwrap();
+        }
+
+        Err(cycle.into_iter().rev().map(|i| self.nodes[i].clone()).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>>
+    {
+        // Tarjan's algorithm - components are returned
+        // in reverse topological order
+
+        struct Tarjan
+        {
+            next_index: usize,
+            index: Vec<Option<usize>>,
+            low_link: Vec<usize>,
+            on_stack: Vec<bool>,
+            stack: Vec<usize>,
+            components: Vec<Vec<usize>>,
+        }
+
+        fn visit(v: usize, edges: &[Vec<(usize, impl Clone)>], t: &mut Tarjan)
+        {
+            t.index[v] = Some(t.next_index);
+            t.low_link[v] = t.next_index;
+            t.next_index += 1;
+            t.stack.push(v);
+            t.on_stack[v] = true;
+
+            for (w, _) in edges[v].iter()
+            {
+                match t.index[*w]
+                {
+                    None =>
+                    {
+                        visit(*w, edges, t);
+                        t.low_link[v] = t.low_link[v].min(t.low_link[*w]);
+                    },
+                    Some(w_index) if t.on_stack[*w] =>
+                    {
+                        t.low_link[v] = t.low_link[v].min(w_index);
+                    },
+                    _ => {},
+                }
+            }
+
+            if Some(t.low_link[v]) == t.index[v]
+            {
+                let mut component = Vec::new();
+
+                loop
+                {
+                    let w = t.stack.pop().unwrap();
+                    t.on_stack[w] = false;
+                    component.push(w);
+
+                    if w == v
+                    {
+                        break;
+                    }
+                }
+
+                t.components.push(component);
+            }
+        }
+
+        let n = self.nodes.len();
+
+        let mut t = Tarjan
+        {
+            next_index: 0,
+            index: vec![None; n],
+            low_link: vec![0; n],
+            on_stack: vec![false; n],
+            stack: Vec::new(),
+            components: Vec::new(),
+        };
+
+        for v in 0..n
+        {
+            if t.index[v].is_none()
+            {
+                visit(v, &self.edges, &mut t);
+            }
+        }
+
+        t.components.into_iter()
+            .map(|c| c.into_iter().map(|i| self.nodes[i].clone()).collect())
+            .collect()
+    }
+
+    pub fn bfs_distances(&self, from: &N) -> HashMap<N, usize>
+    {
+        // Ignores the edge weights - every
+        // edge has a distance of 1
+
+        let mut distances = vec![None; self.nodes.len()];
+        let mut queue = VecDeque::new();
+
+        if let Some(start) = self.indices.get(from)
+        {
+            distances[*start] = Some(0);
+            queue.push_back(*start);
+        }
+
+        while let Some(i) = queue.pop_front()
+        {
+            let next_dist = distances[i].unwrap() + 1;
+
+            for (to, _) in self.edges[i].iter()
+            {
+                if distances[*to].is_none()
+                {
+                    distances[*to] = Some(next_dist);
+                    queue.push_back(*to);
+                }
+            }
+        }
+
+        self.collect_distances(distances)
+    }
+
+    pub fn count_paths(&self, from: &N, weight: impl Fn(&E) -> u64) -> Option<HashMap<N, u64>>
+    {
+        // For a DAG, the number of paths from the start
+        // to each reachable node, where each edge can
+        // count as several (weighted) paths. Returns None
+        // if the graph has a cycle.
+
+        let order = self.topological_sort().ok()?;
+        let mut counts = vec![None; self.nodes.len()];
+
+        if let Some(start) = self.indices.get(from)
+        {
+            counts[*start] = Some(1);
+        }
+
+        for node in order.iter()
+        {
+            let i = self.indices[node];
+
+            if let Some(count) = counts[i]
+            {
+                for (to, e) in self.edges[i].iter()
+                {
+                    counts[*to] = Some(counts[*to].unwrap_or(0) + count * weight(e));
+                }
+            }
+        }
+
+        Some(self.collect_distances(counts))
+    }
+
+    fn collect_distances<D>(&self, distances: Vec<Option<D>>) -> HashMap<N, D>
+    {
+        distances.into_iter()
+            .enumerate()
+            .filter_map(|(i, d)| d.map(|d| (self.nodes[i].clone(), d)))
+            .collect()
+    }
+}
+
+impl<N, E> Graph<N, E>
+    where N: Hash + Eq + Clone,
+        E: Copy + Ord + Add<Output = E> + Zero,
+{
+    #[allow(dead_code)]
+    pub fn dijkstra(&self, from: &N) -> HashMap<N, E>
+    {
+        // Edge weights must not be negative
+
+        let mut distances: Vec<Option<E>> = vec![None; self.nodes.len()];
+        let mut heap = BinaryHeap::new();
+
+        if let Some(start) = self.indices.get(from)
+        {
+            heap.push(Reverse((E::zero(), *start)));
+        }
+
+        while let Some(Reverse((dist, i))) = heap.pop()
+        {
+            if distances[i].is_some()
+            {
+                continue;
+            }
+
+            distances[i] = Some(dist);
+
+            for (to, e) in self.edges[i].iter()
+            {
+                if distances[*to].is_none()
+                {
+                    heap.push(Reverse((dist + *e, *to)));
+                }
+            }
+        }
+
+        self.collect_distances(distances)
+    }
+
+    #[allow(dead_code)]
+    pub fn all_pairs_distances(&self) -> HashMap<(N, N), E>
+    {
+        // Dijkstra from every node - better than
+        // Floyd-Warshall for large sparse graphs
+
+        self.nodes.iter()
+            .flat_map(|from| self.dijkstra(from)
+                .into_iter()
+                .map(move |(to, d)| ((from.clone(), to), d)))
+            .collect()
+    }
+
+    pub fn floyd_warshall(&self) -> HashMap<(N, N), E>
+    {
+        let n = self.nodes.len();
+        let mut dist: Vec<Vec<Option<E>>> = vec![vec![None; n]; n];
+
+        for (from, out) in self.edges.iter().enumerate()
+        {
+            dist[from][from] = Some(E::zero());
+
+            for (to, e) in out.iter()
+            {
+                if dist[from][*to].map(|d| *e < d).unwrap_or(true)
+                {
+                    dist[from][*to] = Some(*e);
+                }
+            }
+        }
+
+        for k in 0..n
+        {
+            let via = dist[k].clone();
+
+            for row in dist.iter_mut()
+            {
+                if let Some(ik) = row[k]
+                {
+                    for (d, kj) in row.iter_mut().zip(via.iter())
+                    {
+                        if let Some(kj) = kj
+                        {
+                            if d.map(|d| ik + *kj < d).unwrap_or(true)
+                            {
+                                *d = Some(ik + *kj);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut result = HashMap::new();
+
+        for (i, row) in dist.into_iter().enumerate()
+        {
+            for (j, d) in row.into_iter().enumerate()
+            {
+                if let Some(d) = d
+                {
+                    result.insert((self.nodes[i].clone(), self.nodes[j].clone()), d);
+                }
+            }
+        }
+
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn min_cut(&self) -> Option<(E, Vec<N>)>
+    {
+        // Stoer-Wagner, treating the edges as undirected.
+        // Returns the total weight of the cheapest set
+        // of edges that splits the graph in two, and the
+        // nodes on one side. This is O(N^3), so only
+        // suitable for a few hundred nodes.
+
+        let n = self.nodes.len();
+
+        if n < 2
+        {
+            return None;
+        }
+
+        let mut weights: Vec<Vec<E>> = vec![vec![E::zero(); n]; n];
+
+        for (from, out) in self.edges.iter().enumerate()
+        {
+            for (to, e) in out.iter()
+            {
+                if from != *to
+                {
+                    weights[from][*to] = weights[from][*to] + *e;
+
+                    if self.directed
+                    {
+                        weights[*to][from] = weights[*to][from] + *e;
+                    }
+                }
+            }
+        }
+
+        // Each remaining node stands for the set of
+        // original nodes that have been merged into it
+
+        let mut merged = (0..n).map(|i| vec![i]).collect::<Vec<_>>();
+        let mut active = (0..n).collect::<Vec<_>>();
+        let mut best: Option<(E, Vec<usize>)> = None;
+
+        while active.len() > 1
+        {
+            // Repeatedly add the node most tightly
+            // connected to those added so far
+
+            let mut added = vec![false; n];
+            let mut connection = vec![E::zero(); n];
+            let mut prev = active[0];
+            let mut last = active[0];
+
+            for _ in 0..active.len()
+            {
+                let next = *active.iter()
+                    .filter(|i| !added[**i])
+                    .max_by_key(|i| connection[**i])
+                    .unwrap();
+
+                added[next] = true;
+                prev = last;
+                last = next;
+
+                for i in active.iter()
+                {
+                    if !added[*i]
+                    {
+                        connection[*i] = connection[*i] + weights[next][*i];
+                    }
+                }
+            }
+
+            // The cut between the last node added
+            // and everything else
+
+            let cut = connection[last];
+
+            if best.as_ref().map(|(b, _)| cut < *b).unwrap_or(true)
+            {
+                best = Some((cut, merged[last].clone()));
+            }
+
+            // Merge the last node into the one before
+
+            let last_merged = std::mem::take(&mut merged[last]);
+            merged[prev].extend(last_merged);
+
+            let last_weights = weights[last].clone();
+
+            for (i, w) in last_weights.into_iter().enumerate()
+            {
+                weights[prev][i] = weights[prev][i] + w;
+                weights[i][prev] = weights[prev][i];
+            }
+
+            active.retain(|i| *i != last);
+        }
+
+        best.map(|(cut, side)| (cut, side.into_iter().map(|i| self.nodes[i].clone()).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_graph_topological_sort()
+    {
+        let mut graph = Graph::new_directed();
+
+        for (a, b) in [(5, 11), (7, 11), (7, 8), (3, 8), (3, 10), (11, 2), (11, 9), (11, 10), (8, 9)]
+        {
+            graph.add_edge(a, b, ());
+        }
+
+        let order = graph.topological_sort().unwrap();
+        let pos = |n: i32| order.iter().position(|o| *o == n).unwrap();
+
+        assert_eq!(order.len(), 8);
+        assert!(pos(7) < pos(11) && pos(11) < pos(9) && pos(8) < pos(9) && pos(3) < pos(10));
+
+        graph.add_edge(9, 5, ());
+        graph.add_edge(8, 7, ());
+
+        let cycle = graph.topological_sort().unwrap_err();
+        assert!(cycle.len() >= 2);
+
+        for i in 0..cycle.len()
+        {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(graph.neighbours(&cycle[i]).any(|(to, _)| *to == next));
+        }
+    }
+
+    #[test]
+    fn test_graph_components_and_paths()
+    {
+        let mut graph = Graph::new_directed();
+
+        for (a, b, w) in [('a', 'b', 1), ('b', 'c', 2), ('c', 'a', 3), ('c', 'd', 1), ('d', 'e', 4), ('e', 'd', 1)]
+        {
+            graph.add_edge(a, b, w);
+        }
+
+        let components = graph.strongly_connected_components().into_iter()
+            .map(|c| c.into_iter().collect::<HashSet<_>>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(components, vec![['d', 'e'].iter().copied().collect(), ['a', 'b', 'c'].iter().copied().collect()]);
+
+        let from_a = graph.dijkstra(&'a');
+        assert_eq!(from_a[&'c'], 3);
+        assert_eq!(from_a[&'e'], 8);
+        assert_eq!(graph.bfs_distances(&'a')[&'e'], 4);
+        assert_eq!(graph.reversed().bfs_distances(&'a').len(), 3);
+
+        let all_pairs = graph.all_pairs_distances();
+        assert_eq!(all_pairs, graph.floyd_warshall());
+        assert_eq!(all_pairs[&('a', 'e')], 8);
+        assert!(!all_pairs.contains_key(&('d', 'a')));
+
+        assert_eq!(graph.count_paths(&'a', |_| 1), None);
+    }
+
+    #[test]
+    fn test_graph_count_paths()
+    {
+        // A diamond, where a-b counts twice
+
+        let mut graph = Graph::new_directed();
+        graph.add_edge('a', 'b', 2);
+        graph.add_edge('a', 'c', 1);
+        graph.add_edge('b', 'd', 1);
+        graph.add_edge('c', 'd', 1);
+        graph.add_edge('x', 'a', 1);
+
+        let counts = graph.count_paths(&'a', |e| *e).unwrap();
+
+        assert_eq!(counts[&'d'], 3);
+        assert_eq!(counts[&'a'], 1);
+        assert!(!counts.contains_key(&'x'));
+    }
+
+    #[test]
+    fn test_graph_min_cut()
+    {
+        // The example from Stoer and Wagner's paper
+
+        let mut graph = Graph::new_undirected();
+
+        for (a, b, w) in [(1, 2, 2), (1, 5, 3), (2, 3, 3), (2, 5, 2), (2, 6, 2), (3, 4, 4),
+                          (3, 7, 2), (4, 7, 2), (4, 8, 2), (5, 6, 3), (6, 7, 1), (7, 8, 3)]
+        {
+            graph.add_edge(a, b, w);
+        }
+
+        let (cut, side) = graph.min_cut().unwrap();
+        let side = side.into_iter().collect::<HashSet<_>>();
+
+        assert_eq!(cut, 4);
+        assert!(side == [3, 4, 7, 8].iter().copied().collect() || side == [1, 2, 5, 6].iter().copied().collect());
+    }
+}
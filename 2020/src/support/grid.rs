@@ -0,0 +1,357 @@
+use itertools::Itertools;
+use crate::support::*;
+use std::collections::{HashSet, VecDeque};
+
+#[derive(Hash, PartialEq, Eq, Clone, Debug)]
+pub struct Grid<T>
+{
+    cells: Vec<T>,
+    width: i64,
+    height: i64,
+    default: T,
+}
+
+impl<T: Clone> Grid<T>
+{
+    pub fn new_from_fill(width: usize, height: usize, default: T) -> Self
+    {
+        let cells = vec![default.clone(); width * height];
+        let width = width as i64;
+        let height = height as i64;
+
+        Grid
+        {
+            cells,
+            width,
+            height,
+            default,
+        }
+    }
+
+    pub fn new_from_input<F>(input: &str, default: char, func: F) -> Self
+        where F: Fn(char) -> T
+    {
+        Self::new_from_char_grid(&CharGrid::new_from_input(input, default), func)
+    }
+
+    pub fn new_from_char_grid<F>(grid: &CharGrid, func: F) -> Self
+        where F: Fn(char) -> T
+    {
+        Grid
+        {
+            cells: grid.all_chars().into_iter().map(&func).collect(),
+            width: grid.get_width(),
+            height: grid.get_height(),
+            default: func(grid.get_default()),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn to_char_grid<F>(&self, func: F) -> CharGrid
+        where F: Fn(&T) -> char
+    {
+        let mut result = CharGrid::new_from_fill(self.width as usize, self.height as usize, func(&self.default));
+
+        for p in self.all_points()
+        {
+            result.put_char(&p, func(self.get(&p)));
+        }
+
+        result
+    }
+
+    pub fn get_width(&self) -> i64
+    {
+        self.width
+    }
+
+    pub fn get_height(&self) -> i64
+    {
+        self.height
+    }
+
+    #[allow(dead_code)]
+    pub fn get_default(&self) -> &T
+    {
+        &self.default
+    }
+
+    #[allow(dead_code)]
+    pub fn set_default(&mut self, default: T)
+    {
+        self.default = default
+    }
+
+    pub fn is_point_in_bounds(&self, point: &Point) -> bool
+    {
+        point.x >= 0 && point.x < self.width && point.y >= 0 && point.y < self.height
+    }
+
+    pub fn get(&self, point: &Point) -> &T
+    {
+        if self.is_point_in_bounds(point)
+        {
+            &self.cells[(point.y * self.width + point.x) as usize]
+        }
+        else
+        {
+            &self.default
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T>
+    {
+        if self.is_point_in_bounds(point)
+        {
+            Some(&mut self.cells[(point.y * self.width + point.x) as usize])
+        }
+        else
+        {
+            None
+        }
+    }
+
+    pub fn put(&mut self, point: &Point, value: T)
+    {
+        if self.is_point_in_bounds(point)
+        {
+            self.cells[(point.y * self.width + point.x) as usize] = value;
+        }
+    }
+
+    pub fn all_points(&self) -> Vec<Point>
+    {
+        (0..(self.width * self.height))
+            .map(|i| Point::new(i % self.width, i / self.width))
+            .collect()
+    }
+
+    #[allow(dead_code)]
+    pub fn all_values(&self) -> impl Iterator<Item = &T>
+    {
+        self.cells.iter()
+    }
+
+    #[allow(dead_code)]
+    pub fn row(&self, y: i64) -> &[T]
+    {
+        assert!(y >= 0 && y < self.height);
+
+        let start = (y * self.width) as usize;
+        &self.cells[start..(start + self.width as usize)]
+    }
+
+    #[allow(dead_code)]
+    pub fn rows(&self) -> impl Iterator<Item = &[T]>
+    {
+        self.cells.chunks(self.width.max(1) as usize)
+    }
+
+    #[allow(dead_code)]
+    pub fn column(&self, x: i64) -> impl Iterator<Item = &T>
+    {
+        assert!(x >= 0 && x < self.width);
+
+        self.cells.iter()
+            .skip(x as usize)
+            .step_by(self.width as usize)
+    }
+
+    #[allow(dead_code)]
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>>
+    {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    #[allow(dead_code)]
+    pub fn map<U, F>(&self, func: F) -> Grid<U>
+        where F: Fn(&T) -> U
+    {
+        Grid
+        {
+            cells: self.cells.iter().map(&func).collect(),
+            width: self.width,
+            height: self.height,
+            default: func(&self.default),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_cw_90(&self) -> Grid<T>
+    {
+        let mut result = Grid::new_from_fill(self.height as usize, self.width as usize, self.default.clone());
+
+        for y in 0..self.height
+        {
+            for x in 0..self.width
+            {
+                let value = self.get(&Point::new(x, y)).clone();
+                result.put(&Point::new(self.height - 1 - y, x), value);
+            }
+        }
+
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn flip_horizontally(&self) -> Grid<T>
+    {
+        let mut result = Grid::new_from_fill(self.width as usize, self.height as usize, self.default.clone());
+
+        for y in 0..self.height
+        {
+            for x in 0..self.width
+            {
+                let value = self.get(&Point::new(x, y)).clone();
+                result.put(&Point::new(self.width - 1 - x, y), value);
+            }
+        }
+
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn transpose(&self) -> Grid<T>
+    {
+        let mut result = Grid::new_from_fill(self.height as usize, self.width as usize, self.default.clone());
+
+        for y in 0..self.height
+        {
+            for x in 0..self.width
+            {
+                let value = self.get(&Point::new(x, y)).clone();
+                result.put(&Point::new(y, x), value);
+            }
+        }
+
+        result
+    }
+}
+
+impl<T: Clone + PartialEq> Grid<T>
+{
+    #[allow(dead_code)]
+    pub fn find_all(&self, value: &T) -> Vec<Point>
+    {
+        self.all_points().into_iter()
+            .filter(|p| self.get(p) == value)
+            .collect()
+    }
+
+    #[allow(dead_code)]
+    pub fn find_flood_fill_points(&self, start_point: &Point) -> GridArea
+    {
+        let target = self.get(start_point);
+        let mut points = HashSet::new();
+        let mut to_check = VecDeque::new();
+        to_check.push_back(*start_point);
+
+        while let Some(next_to_check) = to_check.pop_front()
+        {
+            if self.is_point_in_bounds(&next_to_check)
+                && (self.get(&next_to_check) == target)
+                && points.insert(next_to_check)
+            {
+                for n in next_to_check.neighbours_4()
+                {
+                    to_check.push_back(n);
+                }
+            }
+        }
+
+        GridArea { points }
+    }
+
+    #[allow(dead_code)]
+    pub fn flood_fill(&mut self, point: &Point, fill: T)
+    {
+        for p in self.find_flood_fill_points(point).points
+        {
+            self.put(&p, fill.clone());
+        }
+    }
+}
+
+impl From<&CharGrid> for Grid<char>
+{
+    fn from(grid: &CharGrid) -> Self
+    {
+        Grid::new_from_char_grid(grid, |c| c)
+    }
+}
+
+impl From<&Grid<char>> for CharGrid
+{
+    fn from(grid: &Grid<char>) -> Self
+    {
+        grid.to_char_grid(|&c| c)
+    }
+}
+
+impl<T: Clone + std::fmt::Display> std::fmt::Display for Grid<T>
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        let text = self.rows()
+            .map(|r| r.iter().map(|v| v.to_string()).collect::<String>())
+            .join("\n");
+
+        fmt.write_str(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_grid()
+    {
+        let grid = Grid::new_from_input("12\n34\n56", '0', |c| c.to_digit(10).unwrap());
+
+        assert_eq!(grid.get_width(), 2);
+        assert_eq!(grid.get_height(), 3);
+        assert_eq!(*grid.get(&Point::new(1, 2)), 6);
+        assert_eq!(*grid.get(&Point::new(2, 2)), 0);
+        assert_eq!(grid.to_string(), "12\n34\n56");
+        assert_eq!(grid.rows().map(|r| r.iter().sum::<u32>()).collect_vec(), vec![3, 7, 11]);
+        assert_eq!(grid.columns().map(|c| c.sum::<u32>()).collect_vec(), vec![9, 12]);
+        assert_eq!(grid.map(|v| v * 2).to_string(), "24\n68\n1012");
+        assert_eq!(grid.transpose().to_string(), "135\n246");
+        assert_eq!(grid.rotate_cw_90().to_string(), "531\n642");
+        assert_eq!(grid.flip_horizontally().to_string(), "21\n43\n65");
+        assert_eq!(Grid::new_from_fill(3, 2, '*').to_string(), "***\n***");
+        assert_eq!(Grid::new_from_fill(0, 2, '*').rows().count(), 0);
+        assert_eq!(Grid::new_from_fill(0, 2, '*').to_string(), "");
+    }
+
+    #[test]
+    fn test_grid_find()
+    {
+        let mut grid = Grid::new_from_input("1121\n1221\n3333", '0', |c| c.to_digit(10).unwrap());
+
+        assert_eq!(grid.find_all(&2), vec![Point::new(2, 0), Point::new(1, 1), Point::new(2, 1)]);
+        assert_eq!(grid.find_flood_fill_points(&Point::new(0, 0)).area(), 3);
+        assert_eq!(grid.find_flood_fill_points(&Point::new(3, 0)).area(), 2);
+
+        grid.flood_fill(&Point::new(1, 1), 9);
+        assert_eq!(grid.to_string(), "1191\n1991\n3333");
+    }
+
+    #[test]
+    fn test_grid_char_grid_conversions()
+    {
+        let chars = CharGrid::new_from_input("#.\n.#", '.');
+        let grid = Grid::from(&chars);
+
+        assert_eq!(*grid.get(&Point::new(1, 1)), '#');
+        assert_eq!(*grid.get_default(), '.');
+        assert!(CharGrid::from(&grid) == chars);
+
+        let bools = Grid::new_from_char_grid(&chars, |c| c == '#');
+        assert_eq!(bools.find_all(&true), vec![Point::new(0, 0), Point::new(1, 1)]);
+        assert_eq!(bools.to_char_grid(|&b| if b { 'X' } else { ' ' }).to_string(), "X \n X");
+    }
+}
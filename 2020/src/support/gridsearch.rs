@@ -0,0 +1,432 @@
+use crate::support::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+#[allow(dead_code)]
+pub fn bfs_distance_map<I, W>(sources: I, walkable: W) -> HashMap<Point, usize>
+    where I: IntoIterator<Item = Point>,
+        W: Fn(&Point) -> bool
+{
+    bfs_distance_map_with_steps(sources, |_from, to| walkable(to))
+}
+
+pub fn bfs_distance_map_with_steps<I, FS>(sources: I, can_step: FS) -> HashMap<Point, usize>
+    where I: IntoIterator<Item = Point>,
+        FS: Fn(&Point, &Point) -> bool
+{
+    // Breadth-first search out from all sources at once,
+    // so each point gets the distance to its nearest source.
+    // The sources themselves are assumed to be walkable.
+
+    let mut distances = HashMap::new();
+    let mut to_visit = VecDeque::new();
+
+    for source in sources
+    {
+        if distances.insert(source, 0).is_none()
+        {
+            to_visit.push_back(source);
+        }
+    }
+
+    while let Some(cur) = to_visit.pop_front()
+    {
+        let next_distance = distances[&cur] + 1;
+
+        for next in cur.neighbours_4()
+        {
+            if !distances.contains_key(&next) && can_step(&cur, &next)
+            {
+                distances.insert(next, next_distance);
+                to_visit.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+pub struct ShortestPaths<S>
+{
+    // A DAG of every least-cost path from the start
+    // state(s) to the goal state(s). Each state stores
+    // all of its predecessors that are on a least-cost route.
+
+    cost: usize,
+    states: Vec<S>,
+    costs: Vec<usize>,
+    predecessors: Vec<Vec<usize>>,
+    ends: Vec<usize>,
+}
+
+impl<S: Clone + Eq + Hash> ShortestPaths<S>
+{
+    pub fn cost(&self) -> usize
+    {
+        self.cost
+    }
+
+    #[allow(dead_code)]
+    pub fn ends(&self) -> Vec<S>
+    {
+        self.ends.iter()
+            .map(|&i| self.states[i].clone())
+            .collect()
+    }
+
+    pub fn states_on_paths(&self) -> HashSet<S>
+    {
+        self.indexes_on_paths().into_iter()
+            .map(|i| self.states[i].clone())
+            .collect()
+    }
+
+    #[allow(dead_code)]
+    pub fn one_path(&self) -> Vec<S>
+    {
+        let mut result = Vec::new();
+        let mut cur = self.ends[0];
+
+        loop
+        {
+            result.push(self.states[cur].clone());
+
+            match self.predecessors[cur].first()
+            {
+                Some(&prev) => cur = prev,
+                None => break,
+            }
+        }
+
+        result.reverse();
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn num_paths(&self) -> usize
+    {
+        // Count the paths in order of increasing cost,
+        // so all predecessors are counted before each state
+
+        let mut indexes = self.indexes_on_paths().into_iter().collect::<Vec<_>>();
+        indexes.sort_by_key(|&i| self.costs[i]);
+
+        let mut counts = HashMap::new();
+
+        for i in indexes
+        {
+            let count = if self.predecessors[i].is_empty()
+            {
+                1
+            }
+            else
+            {
+                self.predecessors[i].iter()
+                    .map(|p| counts.get(p).copied().unwrap_or(0))
+                    .sum()
+            };
+
+            counts.insert(i, count);
+        }
+
+        self.ends.iter()
+            .map(|e| counts[e])
+            .sum()
+    }
+
+    fn indexes_on_paths(&self) -> HashSet<usize>
+    {
+        let mut result = HashSet::new();
+        let mut to_visit = self.ends.clone();
+
+        while let Some(i) = to_visit.pop()
+        {
+            if result.insert(i)
+            {
+                to_visit.extend(self.predecessors[i].iter().copied());
+            }
+        }
+
+        result
+    }
+}
+
+pub fn dijkstra_all_paths<S, I, FN, IN, FG>(starts: I, successors: FN, is_goal: FG) -> Option<ShortestPaths<S>>
+    where S: Clone + Eq + Hash,
+        I: IntoIterator<Item = S>,
+        FN: Fn(&S) -> IN,
+        IN: IntoIterator<Item = (S, usize)>,
+        FG: Fn(&S) -> bool
+{
+    // A standard Dijkstra, but keeping every equal-cost
+    // predecessor, and finishing once all goals at the
+    // least cost have been found. States are stored by index
+    // so they don't need to be Ord to go in the heap.
+
+    let mut indexes: HashMap<S, usize> = HashMap::new();
+    let mut result = ShortestPaths
+    {
+        cost: 0,
+        states: Vec::new(),
+        costs: Vec::new(),
+        predecessors: Vec::new(),
+        ends: Vec::new(),
+    };
+    let mut done = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts
+    {
+        if !indexes.contains_key(&start)
+        {
+            indexes.insert(start.clone(), result.states.len());
+            heap.push((Reverse(0), result.states.len()));
+            result.states.push(start);
+            result.costs.push(0);
+            result.predecessors.push(Vec::new());
+            done.push(false);
+        }
+    }
+
+    let mut found_cost = None;
+
+    while let Some((Reverse(cost), index)) = heap.pop()
+    {
+        if done[index] || (cost > result.costs[index])
+        {
+            continue;
+        }
+
+        if let Some(found_cost) = found_cost
+        {
+            if cost > found_cost
+            {
+                break;
+            }
+        }
+
+        done[index] = true;
+
+        if is_goal(&result.states[index])
+        {
+            found_cost = Some(cost);
+            result.ends.push(index);
+            continue;
+        }
+
+        for (next, step_cost) in successors(&result.states[index])
+        {
+            let next_cost = cost + step_cost;
+
+            match indexes.get(&next)
+            {
+                None =>
+                {
+                    indexes.insert(next.clone(), result.states.len());
+                    heap.push((Reverse(next_cost), result.states.len()));
+                    result.states.push(next);
+                    result.costs.push(next_cost);
+                    result.predecessors.push(vec![index]);
+                    done.push(false);
+                },
+                Some(&next_index) =>
+                {
+                    if next_cost < result.costs[next_index]
+                    {
+                        result.costs[next_index] = next_cost;
+                        result.predecessors[next_index] = vec![index];
+                        heap.push((Reverse(next_cost), next_index));
+                    }
+                    else if (next_cost == result.costs[next_index]) && !done[next_index]
+                    {
+                        result.predecessors[next_index].push(index);
+                    }
+                },
+            }
+        }
+    }
+
+    result.cost = found_cost?;
+    Some(result)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GridState
+{
+    pub pos: Point,
+    pub dir: Point,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GridMove
+{
+    Forward,
+    TurnLeft,
+    TurnRight,
+}
+
+impl GridState
+{
+    pub fn new(pos: Point, dir: Point) -> Self
+    {
+        GridState { pos, dir }
+    }
+
+    pub fn apply(&self, grid_move: GridMove) -> GridState
+    {
+        match grid_move
+        {
+            GridMove::Forward => GridState::new(self.pos + self.dir, self.dir),
+            GridMove::TurnLeft => GridState::new(self.pos, self.dir.rotate_90_left()),
+            GridMove::TurnRight => GridState::new(self.pos, self.dir.rotate_90_right()),
+        }
+    }
+}
+
+pub fn dijkstra_directional<W, FC, FG>(start: GridState, walkable: W, move_cost: FC, is_goal: FG) -> Option<ShortestPaths<GridState>>
+    where W: Fn(&Point) -> bool,
+        FC: Fn(&GridState, GridMove) -> Option<usize>,
+        FG: Fn(&GridState) -> bool
+{
+    // Each state is a position and facing direction.
+    // The cost callback can disallow a move by returning None.
+
+    dijkstra_all_paths(
+        std::iter::once(start),
+        |state|
+        {
+            [GridMove::Forward, GridMove::TurnLeft, GridMove::TurnRight].iter()
+                .filter_map(|&m|
+                {
+                    let next = state.apply(m);
+
+                    if (m == GridMove::Forward) && !walkable(&next.pos)
+                    {
+                        return None;
+                    }
+
+                    move_cost(state, m).map(|cost| (next, cost))
+                })
+                .collect::<Vec<_>>()
+        },
+        is_goal)
+}
+
+#[allow(dead_code)]
+pub fn find_junctions<I, W>(points: I, walkable: W) -> Vec<Point>
+    where I: IntoIterator<Item = Point>,
+        W: Fn(&Point) -> bool
+{
+    // Junctions are walkable points where
+    // there are three or more ways to go
+
+    points.into_iter()
+        .filter(|p| walkable(p) && (p.neighbours_4().filter(|n| walkable(n)).count() >= 3))
+        .collect()
+}
+
+#[allow(dead_code)]
+pub fn points_of_interest_graph<W>(points_of_interest: &[Point], walkable: W) -> HashMap<Point, Vec<(Point, usize)>>
+    where W: Fn(&Point) -> bool
+{
+    // Compresses the walkable area into a graph between
+    // the points of interest. An edge is only added if
+    // the route does not pass through another point of interest.
+
+    let pois = points_of_interest.iter().copied().collect::<HashSet<_>>();
+
+    points_of_interest.iter()
+        .map(|&poi|
+        {
+            let distances = bfs_distance_map_with_steps(
+                std::iter::once(poi),
+                |from, to| ((*from == poi) || !pois.contains(from)) && walkable(to));
+
+            let mut edges = distances.into_iter()
+                .filter(|(p, _)| (*p != poi) && pois.contains(p))
+                .collect::<Vec<_>>();
+
+            edges.sort_by_key(|(p, d)| (*d, p.y, p.x));
+
+            (poi, edges)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    const MAZE: &str = "#######\n#S....#\n#.##..#\n#....E#\n#######";
+
+    #[test]
+    fn test_bfs_distance_map()
+    {
+        let grid = CharGrid::new_from_input(MAZE, '#');
+        let walkable = grid.walkable_except("#");
+
+        let distances = bfs_distance_map(std::iter::once(Point::new(1, 1)), &walkable);
+        assert_eq!(distances.len(), 13);
+        assert_eq!(distances[&Point::new(5, 3)], 6);
+
+        let distances = bfs_distance_map(vec![Point::new(1, 1), Point::new(5, 3)], &walkable);
+        assert_eq!(distances[&Point::new(5, 1)], 2);
+        assert_eq!(distances[&Point::new(3, 3)], 2);
+    }
+
+    #[test]
+    fn test_dijkstra_all_paths()
+    {
+        let grid = CharGrid::new_from_input(MAZE, '#');
+        let walkable = grid.walkable_except("#");
+        let end = Point::new(5, 3);
+
+        let paths = dijkstra_all_paths(
+            std::iter::once(Point::new(1, 1)),
+            |p| p.neighbours_4().filter(|n| walkable(n)).map(|n| (n, 1)).collect::<Vec<_>>(),
+            |p| *p == end).unwrap();
+
+        assert_eq!(paths.cost(), 6);
+        assert_eq!(paths.one_path().len(), 7);
+        assert_eq!(paths.num_paths(), 4);
+        assert_eq!(paths.states_on_paths().len(), 13);
+    }
+
+    #[test]
+    fn test_dijkstra_directional()
+    {
+        let grid = CharGrid::new_from_input(MAZE, '#');
+        let end = Point::new(5, 3);
+
+        let paths = dijkstra_directional(
+            GridState::new(Point::new(1, 1), Point::new(1, 0)),
+            grid.walkable_except("#"),
+            |_, m| Some(if m == GridMove::Forward { 1 } else { 1000 }),
+            |s| s.pos == end).unwrap();
+
+        assert_eq!(paths.cost(), 1006);
+        assert_eq!(paths.ends().len(), 1);
+
+        let points = paths.states_on_paths().into_iter().map(|s| s.pos).collect::<HashSet<_>>();
+        assert_eq!(points.len(), 7);
+    }
+
+    #[test]
+    fn test_points_of_interest_graph()
+    {
+        let grid = CharGrid::new_from_input(MAZE, '#');
+        let walkable = grid.walkable_except("#");
+
+        let junctions = find_junctions(grid.all_points(), &walkable);
+        assert_eq!(junctions, vec![Point::new(4, 1), Point::new(4, 2), Point::new(5, 2), Point::new(4, 3)]);
+
+        let pois = vec![Point::new(1, 1), Point::new(3, 3), Point::new(5, 3)];
+        let graph = points_of_interest_graph(&pois, &walkable);
+
+        assert_eq!(graph[&Point::new(1, 1)], vec![(Point::new(3, 3), 4), (Point::new(5, 3), 6)]);
+        assert_eq!(graph[&Point::new(3, 3)], vec![(Point::new(5, 3), 2), (Point::new(1, 1), 4)]);
+        assert_eq!(graph[&Point::new(5, 3)], vec![(Point::new(3, 3), 2), (Point::new(1, 1), 6)]);
+    }
+}
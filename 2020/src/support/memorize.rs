@@ -1,16 +1,144 @@
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::collections::hash_map::RandomState;
 use std::fmt::Debug;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
 use std::cell::RefCell;
+use std::sync::Mutex;
+use std::thread::LocalKey;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats
+{
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+}
+
+impl MemoStats
+{
+    fn add(&self, other: &MemoStats) -> MemoStats
+    {
+        MemoStats
+        {
+            hits: self.hits + other.hits,
+            misses: self.misses + other.misses,
+            evictions: self.evictions + other.evictions,
+            entries: self.entries + other.entries,
+        }
+    }
+}
+
+struct Cache<I, O>
+    where I: Hash + Eq + Clone,
+        O: Clone,
+{
+    // With a capacity, each entry remembers when it
+    // was last used, and the least recently used
+    // entry is evicted to make room for a new one
+
+    map: HashMap<I, (O, u64)>,
+    last_used: BTreeMap<u64, I>,
+    tick: u64,
+    capacity: Option<usize>,
+    stats: MemoStats,
+}
+
+impl<I, O> Cache<I, O>
+    where I: Hash + Eq + Clone,
+        O: Clone,
+{
+    fn new(capacity: Option<usize>) -> Self
+    {
+        Cache
+        {
+            map: HashMap::new(),
+            last_used: BTreeMap::new(),
+            tick: 0,
+            capacity,
+            stats: MemoStats::default(),
+        }
+    }
+
+    fn get(&mut self, input: &I) -> Option<O>
+    {
+        let tick = self.tick + 1;
+
+        match self.map.get_mut(input)
+        {
+            Some((output, used)) =>
+            {
+                self.stats.hits += 1;
+
+                if self.capacity.is_some()
+                {
+                    self.last_used.remove(used);
+                    self.last_used.insert(tick, input.clone());
+                    *used = tick;
+                    self.tick = tick;
+                }
+
+                Some(output.clone())
+            },
+            None =>
+            {
+                self.stats.misses += 1;
+                None
+            },
+        }
+    }
+
+    fn insert(&mut self, input: I, output: O)
+    {
+        if let Some(capacity) = self.capacity
+        {
+            if !self.map.contains_key(&input)
+            {
+                if capacity == 0
+                {
+                    return;
+                }
+
+                if self.map.len() >= capacity
+                {
+                    let (_, oldest) = self.last_used.pop_first().unwrap();
+                    self.map.remove(&oldest);
+                    self.stats.evictions += 1;
+                }
+            }
+
+            self.tick += 1;
+            self.last_used.insert(self.tick, input.clone());
+        }
+
+        if let Some((_, used)) = self.map.insert(input, (output, self.tick))
+        {
+            if self.capacity.is_some()
+            {
+                self.last_used.remove(&used);
+            }
+        }
+    }
+
+    fn clear(&mut self)
+    {
+        self.map.clear();
+        self.last_used.clear();
+    }
+
+    fn stats(&self) -> MemoStats
+    {
+        MemoStats { entries: self.map.len(), ..self.stats }
+    }
+}
 
 struct Data<I, O>
     where I: Hash + Eq + Clone + Debug,
         O: Clone + Debug,
 {
-    cache: HashMap<I, O>,
+    cache: Cache<I, O>,
     indent: usize,
-    num_memorized: u64,
-    num_calculated: u64,
 }
 
 pub struct Memorized<'a, I, O>
@@ -34,10 +162,8 @@ impl<'a, I, O> Memorized<'a, I, O>
             func,
             debug: false,
             data: RefCell::new(Data{
-                cache: HashMap::new(),
-                indent: 0,
-                num_memorized: 0,
-                num_calculated: 0}),
+                cache: Cache::new(None),
+                indent: 0}),
         }
     }
 
@@ -48,15 +174,22 @@ impl<'a, I, O> Memorized<'a, I, O>
         self
     }
 
+    #[allow(dead_code)]
+    pub fn capacity(self, capacity: usize) -> Self
+    {
+        // Only keep the most recently used results
+
+        self.data.borrow_mut().cache = Cache::new(Some(capacity));
+        self
+    }
+
     pub fn get(&self, input: &I) -> O
     {
         {
             let mut data = self.data.borrow_mut();
 
-            if let Some(output) = data.cache.get(input).cloned()
+            if let Some(output) = data.cache.get(input)
             {
-                data.num_memorized += 1;
-
                 if self.debug
                 {
                     let indent = vec![' '; data.indent].into_iter().collect::<String>();
@@ -74,7 +207,6 @@ impl<'a, I, O> Memorized<'a, I, O>
         let mut data = self.data.borrow_mut();
 
         data.indent -= 1;
-        data.num_calculated += 1;
 
         if self.debug
         {
@@ -87,16 +219,230 @@ impl<'a, I, O> Memorized<'a, I, O>
         output
     }
 
+    #[allow(dead_code)]
+    pub fn seed(&self, input: I, output: O)
+    {
+        self.data.borrow_mut().cache.insert(input, output);
+    }
+
+    #[allow(dead_code)]
+    pub fn clear(&self)
+    {
+        self.data.borrow_mut().cache.clear();
+    }
+
+    #[allow(dead_code)]
+    pub fn stats(&self) -> MemoStats
+    {
+        self.data.borrow().cache.stats()
+    }
+
     #[allow(unused)]
     pub fn print_stats(&self)
     {
-        let data = self.data.borrow();
+        let stats = self.stats();
+
+        println!("Memorized:  {}", stats.hits);
+        println!("Calculated: {}", stats.misses);
+    }
+}
+
+const NUM_SHARDS: usize = 16;
+
+pub struct SyncMemorized<'a, I, O>
+    where I: Hash + Eq + Clone + Send,
+        O: Clone + Send,
+{
+    // Can be shared between threads. Each shard
+    // is only locked while looking up or storing
+    // a result, so two threads may both calculate
+    // the same missing result.
+
+    func: &'a (dyn Fn(&I, &SyncMemorized<'a, I, O>) -> O + Sync),
+    hasher: RandomState,
+    shards: Vec<Mutex<Cache<I, O>>>,
+}
+
+impl<'a, I, O> SyncMemorized<'a, I, O>
+    where I: Hash + Eq + Clone + Send,
+        O: Clone + Send,
+{
+    pub fn new<F>(func: &'a F) -> Self
+        where F: 'a + Sync + Fn(&I, &SyncMemorized<'a, I, O>) -> O
+    {
+        SyncMemorized
+        {
+            func,
+            hasher: RandomState::new(),
+            shards: (0..NUM_SHARDS).map(|_| Mutex::new(Cache::new(None))).collect(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn capacity(mut self, capacity: usize) -> Self
+    {
+        // Split evenly between the shards, so the
+        // eviction order is only approximately LRU
+
+        let per_shard = capacity.div_ceil(NUM_SHARDS);
+
+        self.shards = (0..NUM_SHARDS).map(|_| Mutex::new(Cache::new(Some(per_shard)))).collect();
+        self
+    }
+
+    fn shard(&self, input: &I) -> &Mutex<Cache<I, O>>
+    {
+        &self.shards[(self.hasher.hash_one(input) as usize) % NUM_SHARDS]
+    }
+
+    pub fn get(&self, input: &I) -> O
+    {
+        if let Some(output) = self.shard(input).lock().unwrap().get(input)
+        {
+            return output;
+        }
+
+        let output = (self.func)(input, self);
+
+        self.shard(input).lock().unwrap().insert(input.clone(), output.clone());
+
+        output
+    }
+
+    #[allow(dead_code)]
+    pub fn seed(&self, input: I, output: O)
+    {
+        self.shard(&input).lock().unwrap().insert(input, output);
+    }
 
-        println!("Memorized:  {}", data.num_memorized);
-        println!("Calculated: {}", data.num_calculated);
+    #[allow(dead_code)]
+    pub fn clear(&self)
+    {
+        for shard in self.shards.iter()
+        {
+            shard.lock().unwrap().clear();
+        }
     }
+
+    #[allow(dead_code)]
+    pub fn stats(&self) -> MemoStats
+    {
+        self.shards.iter()
+            .fold(MemoStats::default(), |total, shard| total.add(&shard.lock().unwrap().stats()))
+    }
+}
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct ScopedCache
+{
+    depth: usize,
+    cache: Option<Box<dyn Any>>,
+}
+
+#[doc(hidden)]
+pub fn memorized_call<K, O, F>(local: &'static LocalKey<RefCell<ScopedCache>>, key: K, calc: F) -> O
+    where K: Hash + Eq + Clone + 'static,
+        O: Clone + 'static,
+        F: FnOnce() -> O,
+{
+    // Used by memorize! - the cache is thrown away
+    // when the outermost call returns
+
+    let found = local.with(|scoped|
+    {
+        let mut scoped = scoped.borrow_mut();
+        scoped.depth += 1;
+
+        scoped.cache.as_mut()
+            .and_then(|c| c.downcast_mut::<Cache<K, O>>())
+            .and_then(|c| c.get(&key))
+    });
+
+    let output = match found
+    {
+        Some(output) => output,
+        None =>
+        {
+            let output = calc();
+
+            local.with(|scoped|
+            {
+                scoped.borrow_mut().cache
+                    .get_or_insert_with(|| Box::new(Cache::<K, O>::new(None)))
+                    .downcast_mut::<Cache<K, O>>()
+                    .unwrap()
+                    .insert(key, output.clone());
+            });
+
+            output
+        },
+    };
+
+    local.with(|scoped|
+    {
+        let mut scoped = scoped.borrow_mut();
+        scoped.depth -= 1;
+
+        if scoped.depth == 0
+        {
+            scoped.cache = None;
+        }
+    });
+
+    output
+}
+
+// Memorizes a plain function. Recursive calls go
+// through the cache, which only lasts for the
+// outermost call - so arguments that don't change
+// during the recursion, such as a map, can be left
+// out of the key:
+//
+// memorize!
+// {
+//     #[key(springs.len(), remaining)]
+//     fn count(map: &Map, springs: &[Spring], remaining: usize) -> usize
+//     {
+//         ...
+//     }
+// }
+//
+// Without a key, all of the arguments are cloned
+// into the key.
+
+macro_rules! memorize
+{
+    (
+        #[key($($key:expr),+ $(,)?)]
+        $vis:vis fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty $body:block
+    ) =>
+    {
+        $vis fn $name($($arg: $ty),*) -> $ret
+        {
+            thread_local!
+            {
+                static CACHE: std::cell::RefCell<$crate::support::memorize::ScopedCache> = Default::default();
+            }
+
+            $crate::support::memorize::memorized_call(&CACHE, ($($key,)+), move || -> $ret { $body })
+        }
+    };
+
+    (
+        $vis:vis fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty $body:block
+    ) =>
+    {
+        memorize!
+        {
+            #[key($($arg.clone()),*)]
+            $vis fn $name($($arg: $ty),*) -> $ret $body
+        }
+    };
 }
 
+pub(crate) use memorize;
+
 #[test]
 fn test_memorized()
 {
@@ -122,3 +468,81 @@ fn test_memorized()
     assert_eq!(fibonacchi.get(&8), 21);
     assert_eq!(fibonacchi.get(&92), 7540113804746346429u64);
 }
+
+#[test]
+fn test_memorized_capacity_and_stats()
+{
+    let squares = Memorized::new(&|n: &u64, _: &Memorized<u64, u64>| n * n).capacity(2);
+
+    squares.seed(10, 1000);
+    assert_eq!(squares.get(&10), 1000);
+
+    squares.get(&1);
+    squares.get(&10);
+    squares.get(&2);
+
+    // 1 was least recently used, so was evicted
+
+    squares.get(&1);
+
+    assert_eq!(squares.stats(), MemoStats { hits: 2, misses: 3, evictions: 2, entries: 2 });
+
+    squares.clear();
+    assert_eq!(squares.get(&10), 100);
+}
+
+#[test]
+fn test_sync_memorized()
+{
+    use rayon::prelude::*;
+
+    let fibonacchi = SyncMemorized::new(
+        &|target: &u64, fibonacchi: &SyncMemorized<u64, u64>| -> u64
+        {
+            match *target
+            {
+                0 => 0,
+                1 => 1,
+                _ => fibonacchi.get(&(*target - 2)) + fibonacchi.get(&(*target - 1)),
+            }
+        });
+
+    let results = (0..93u64).into_par_iter().map(|n| fibonacchi.get(&n)).collect::<Vec<_>>();
+
+    assert_eq!(results[8], 21);
+    assert_eq!(results[92], 7540113804746346429u64);
+    assert_eq!(fibonacchi.stats().entries, 93);
+}
+
+#[test]
+fn test_memorize_macro()
+{
+    memorize!
+    {
+        fn fibonacchi(n: u64) -> u64
+        {
+            if n < 2 { n } else { fibonacchi(n - 2) + fibonacchi(n - 1) }
+        }
+    }
+
+    memorize!
+    {
+        #[key(a.len(), b.len())]
+        fn common(a: &[char], b: &[char]) -> usize
+        {
+            match (a.first(), b.first())
+            {
+                (Some(x), Some(y)) if x == y => 1 + common(&a[1..], &b[1..]),
+                (Some(_), Some(_)) => common(&a[1..], b).max(common(a, &b[1..])),
+                _ => 0,
+            }
+        }
+    }
+
+    assert_eq!(fibonacchi(92), 7540113804746346429u64);
+
+    let chars = |s: &str| s.chars().collect::<Vec<_>>();
+
+    assert_eq!(common(&chars("ABCBDAB"), &chars("BDCABA")), 4);
+    assert_eq!(common(&chars("XMJYAUZ"), &chars("MZJAWXU")), 4);
+}
@@ -1,21 +1,34 @@
 pub mod alg;
 pub mod chargrid;
 pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod gridsearch;
 pub mod input;
 pub mod memorize;
 pub mod num;
+pub mod ocr;
+pub mod patterns;
+pub mod regions;
 pub mod runtime;
 pub mod scan;
+pub mod sparsegrid;
 pub mod string;
 pub mod tuple_append;
 
 pub use alg::*;
 pub use chargrid::*;
 pub use geom::*;
+pub use graph::*;
+pub use grid::*;
+pub use gridsearch::*;
 pub use input::*;
 pub use memorize::*;
 pub use num::*;
+pub use patterns::*;
+pub use regions::*;
 pub use runtime::*;
 pub use scan::*;
+pub use sparsegrid::*;
 pub use string::*;
 pub use tuple_append::*;
@@ -1,20 +1,35 @@
 pub mod diophantine;
+pub mod modular;
+pub mod primes;
 
-pub fn gcd(a: u64, b: u64) -> u64
+pub use modular::*;
+#[allow(unused)]
+pub use primes::*;
+
+use num_traits::PrimInt;
+
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T
 {
-    if b == 0
+    let result = if b == T::zero()
     {
         a
     }
     else
     {
         gcd(b, a % b)
-    }
+    };
+
+    if result < T::zero() { T::zero() - result } else { result }
 }
 
-pub fn lcm(a: u64, b: u64) -> u64
+pub fn lcm<T: PrimInt>(a: T, b: T) -> T
 {
-    a * b / gcd(a, b)
+    if (a == T::zero()) || (b == T::zero())
+    {
+        return T::zero();
+    }
+
+    a / gcd(a, b) * b
 }
 
 #[cfg(test)]
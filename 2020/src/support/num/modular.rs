@@ -0,0 +1,317 @@
+use num_traits::{PrimInt, NumCast};
+
+// Everything is worked out in i128, so works the same
+// for i64, u64 and i128 - as long as the values fit
+
+fn wide<T: PrimInt>(val: T) -> i128
+{
+    val.to_i128().expect("Value doesn't fit in an i128")
+}
+
+fn narrow<T: PrimInt>(val: i128) -> T
+{
+    <T as NumCast>::from(val).expect("Result doesn't fit in the result type")
+}
+
+fn mul_mod_wide(a: i128, b: i128, modulus: i128) -> i128
+{
+    // a and b are already in 0..modulus. If the product
+    // would overflow, double and add instead - in u128,
+    // where doubling anything below the modulus fits.
+
+    if let Some(product) = a.checked_mul(b)
+    {
+        return product % modulus;
+    }
+
+    let modulus = modulus as u128;
+    let mut a = a as u128;
+    let mut b = b as u128;
+    let mut result = 0u128;
+
+    while b > 0
+    {
+        if (b & 1) != 0
+        {
+            result = (result + a) % modulus;
+        }
+        a = (a + a) % modulus;
+        b >>= 1;
+    }
+
+    result as i128
+}
+
+pub fn extended_gcd<T: PrimInt>(a: T, b: T) -> (T, i128, i128)
+{
+    // Returns (g, x, y) with a*x + b*y = g, where
+    // g is the non-negative gcd of a and b
+
+    let (mut old_r, mut r) = (wide(a), wide(b));
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0
+    {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0
+    {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+
+    (narrow(old_r), old_x, old_y)
+}
+
+pub fn mod_inverse<T: PrimInt>(a: T, modulus: T) -> Option<T>
+{
+    // The x in 0..modulus with a*x = 1, if
+    // a and the modulus are coprime
+
+    let (g, x, _) = extended_gcd(a, modulus);
+
+    if g != T::one()
+    {
+        return None;
+    }
+
+    Some(narrow(x.rem_euclid(wide(modulus))))
+}
+
+#[allow(dead_code)]
+pub fn mul_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T
+{
+    let modulus = wide(modulus);
+
+    narrow(mul_mod_wide(wide(a).rem_euclid(modulus), wide(b).rem_euclid(modulus), modulus))
+}
+
+#[allow(dead_code)]
+pub fn pow_mod<T: PrimInt>(base: T, mut exp: u64, modulus: T) -> T
+{
+    let modulus = wide(modulus);
+    let mut base = wide(base).rem_euclid(modulus);
+    let mut result = 1 % modulus;
+
+    while exp > 0
+    {
+        if (exp & 1) != 0
+        {
+            result = mul_mod_wide(result, base, modulus);
+        }
+        base = mul_mod_wide(base, base, modulus);
+        exp >>= 1;
+    }
+
+    narrow(result)
+}
+
+pub fn chinese_remainder<T, I>(congruences: I) -> Option<(T, T)>
+    where T: PrimInt,
+        I: IntoIterator<Item = (T, T)>,
+{
+    // Takes (residue, modulus) pairs, and returns the
+    // (residue, modulus) that satisfies all of them.
+    // The moduli don't need to be coprime - returns
+    // None if the congruences contradict each other.
+
+    let mut residue = 0i128;
+    let mut modulus = 1i128;
+
+    for (r, m) in congruences
+    {
+        let m = wide(m);
+        let r = wide(r).rem_euclid(m);
+
+        let (g, _, _) = extended_gcd(modulus, m);
+        let diff = r - residue;
+
+        if diff % g != 0
+        {
+            return None;
+        }
+
+        // residue + modulus*k = r (mod m), so
+        // k = diff/g * inverse(modulus/g) (mod m/g)
+
+        let reduced = m / g;
+        let inverse = mod_inverse(modulus / g, reduced).unwrap();
+        let k = mul_mod_wide((diff / g).rem_euclid(reduced), inverse, reduced);
+
+        let combined = (modulus / g).checked_mul(m).expect("Combined modulus overflowed");
+
+        residue = (residue + modulus * k).rem_euclid(combined);
+        modulus = combined;
+    }
+
+    Some((narrow(residue), narrow(modulus)))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AffineMod<T: PrimInt>
+{
+    // x -> (mul * x + add) % modulus
+
+    pub mul: T,
+    pub add: T,
+    pub modulus: T,
+}
+
+impl<T: PrimInt> AffineMod<T>
+{
+    pub fn new(mul: T, add: T, modulus: T) -> Self
+    {
+        let m = wide(modulus);
+
+        AffineMod
+        {
+            mul: narrow(wide(mul).rem_euclid(m)),
+            add: narrow(wide(add).rem_euclid(m)),
+            modulus,
+        }
+    }
+
+    pub fn identity(modulus: T) -> Self
+    {
+        AffineMod::new(T::one(), T::zero(), modulus)
+    }
+
+    pub fn apply(&self, x: T) -> T
+    {
+        let m = wide(self.modulus);
+        let x = wide(x).rem_euclid(m);
+
+        narrow((mul_mod_wide(wide(self.mul), x, m) + wide(self.add)) % m)
+    }
+
+    pub fn then(&self, next: &AffineMod<T>) -> AffineMod<T>
+    {
+        // Applies this one, and then the next one
+
+        assert!(self.modulus == next.modulus);
+
+        let m = wide(self.modulus);
+        let next_mul = wide(next.mul);
+
+        AffineMod
+        {
+            mul: narrow(mul_mod_wide(next_mul, wide(self.mul), m)),
+            add: narrow((mul_mod_wide(next_mul, wide(self.add), m) + wide(next.add)) % m),
+            modulus: self.modulus,
+        }
+    }
+
+    pub fn inverse(&self) -> Option<AffineMod<T>>
+    {
+        // y = mul*x + add, so x = inverse(mul) * (y - add)
+
+        let m = wide(self.modulus);
+        let inv = wide(mod_inverse(self.mul, self.modulus)?);
+
+        Some(AffineMod
+        {
+            mul: narrow(inv),
+            add: narrow(mul_mod_wide(inv, (m - wide(self.add)) % m, m)),
+            modulus: self.modulus,
+        })
+    }
+
+    pub fn pow(&self, mut times: u64) -> AffineMod<T>
+    {
+        // Applies this one the given number of
+        // times, by repeated squaring
+
+        let mut result = AffineMod::identity(self.modulus);
+        let mut square = *self;
+
+        while times > 0
+        {
+            if (times & 1) != 0
+            {
+                result = result.then(&square);
+            }
+            square = square.then(&square);
+            times >>= 1;
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_extended_gcd()
+    {
+        assert_eq!(extended_gcd(240i64, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(0u64, 5), (5, 0, 1));
+        assert_eq!(extended_gcd(-12i128, 18).0, 6);
+
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(7u64, 10), Some(3));
+        assert_eq!(mod_inverse(6i64, 9), None);
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+    }
+
+    #[test]
+    fn test_mul_pow_mod()
+    {
+        let big = (1u64 << 63) + 25;
+
+        assert_eq!(mul_mod(big - 1, big - 1, big), 1);
+        assert_eq!(mul_mod(-2i64, 3, 7), 1);
+        assert_eq!(pow_mod(2u64, 10, 1000), 24);
+        assert_eq!(pow_mod(5i64, 0, 1), 0);
+
+        // A modulus too big to multiply in an i128
+
+        let prime = 170141183460469231731687303715884105727i128;
+        assert_eq!(pow_mod(3, (1 << 20) + 1, prime), mul_mod(pow_mod(3, 1 << 20, prime), 3, prime));
+        assert_eq!(mul_mod(prime - 1, prime - 1, prime), 1);
+    }
+
+    #[test]
+    fn test_chinese_remainder()
+    {
+        assert_eq!(chinese_remainder([(2u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(chinese_remainder([(0i64, 17), (-2, 13), (-3, 19)]), Some((3417, 4199)));
+
+        // Not coprime
+
+        assert_eq!(chinese_remainder([(2i64, 6), (8, 9)]), Some((8, 18)));
+        assert_eq!(chinese_remainder([(1i64, 6), (2, 4)]), None);
+        assert_eq!(chinese_remainder(Vec::<(i64, i64)>::new()), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_affine_mod()
+    {
+        let f = AffineMod::new(3i64, -4, 101);
+        let g = AffineMod::new(-7i64, 12, 101);
+
+        assert_eq!(f, AffineMod { mul: 3, add: 97, modulus: 101 });
+        assert_eq!(f.then(&g).apply(50), g.apply(f.apply(50)));
+        assert_eq!(f.inverse().unwrap().apply(f.apply(42)), 42);
+        assert_eq!(AffineMod::new(5u64, 1, 10).inverse(), None);
+
+        let mut x = 17;
+        for times in 0..50
+        {
+            assert_eq!(f.pow(times).apply(17), x);
+            x = f.apply(x);
+        }
+
+        // Big enough to overflow an i64 multiply
+
+        let big = AffineMod::new(119315717514046i64, 55, 119315717514047);
+        assert_eq!(big.pow(2).apply(1), 1);
+        assert_eq!(big.pow(101741582076661).then(&big.inverse().unwrap().pow(101741582076661)), AffineMod::identity(119315717514047));
+    }
+}
@@ -0,0 +1,174 @@
+use super::modular::{mul_mod, pow_mod};
+
+#[allow(dead_code)]
+pub fn primes_up_to(limit: u64) -> Vec<u64>
+{
+    // Sieve of Eratosthenes
+
+    let limit = limit as usize;
+    let mut is_composite = vec![false; limit + 1];
+    let mut result = Vec::new();
+
+    for n in 2..=limit
+    {
+        if is_composite[n]
+        {
+            continue;
+        }
+
+        result.push(n as u64);
+
+        for multiple in (n * n..=limit).step_by(n)
+        {
+            is_composite[multiple] = true;
+        }
+    }
+
+    result
+}
+
+#[allow(dead_code)]
+pub fn is_prime(n: u64) -> bool
+{
+    // Miller-Rabin - these bases are enough to
+    // give the right answer for every u64
+
+    if n < 2
+    {
+        return false;
+    }
+
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    for p in BASES
+    {
+        if n.is_multiple_of(p)
+        {
+            return n == p;
+        }
+    }
+
+    let twos = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> twos;
+
+    BASES.iter().all(|a|
+    {
+        let mut x = pow_mod(*a, odd, n);
+
+        if (x == 1) || (x == n - 1)
+        {
+            return true;
+        }
+
+        for _ in 1..twos
+        {
+            x = mul_mod(x, x, n);
+
+            if x == n - 1
+            {
+                return true;
+            }
+        }
+
+        false
+    })
+}
+
+#[allow(dead_code)]
+pub fn factorise(mut n: u64) -> Vec<(u64, u32)>
+{
+    // (prime, power) pairs in increasing
+    // order, by trial division
+
+    let mut result = Vec::new();
+    let mut p = 2;
+
+    while p <= n / p
+    {
+        if n.is_multiple_of(p)
+        {
+            let mut power = 0;
+
+            while n.is_multiple_of(p)
+            {
+                n /= p;
+                power += 1;
+            }
+
+            result.push((p, power));
+        }
+
+        p += if p == 2 { 1 } else { 2 };
+    }
+
+    if n > 1
+    {
+        result.push((n, 1));
+    }
+
+    result
+}
+
+#[allow(dead_code)]
+pub fn divisors(n: u64) -> Vec<u64>
+{
+    // Every divisor, including 1 and n, in increasing order
+
+    let mut result = vec![1];
+
+    for (p, power) in factorise(n)
+    {
+        let existing = result.len();
+        let mut mult = 1;
+
+        for _ in 0..power
+        {
+            mult *= p;
+
+            for i in 0..existing
+            {
+                result.push(result[i] * mult);
+            }
+        }
+    }
+
+    if n == 0
+    {
+        result.clear();
+    }
+
+    result.sort_unstable();
+    result
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_primes()
+    {
+        assert_eq!(primes_up_to(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes_up_to(1), Vec::<u64>::new());
+
+        let sieved = primes_up_to(10000);
+        assert!((0..=10000).all(|n| is_prime(n) == sieved.binary_search(&n).is_ok()));
+        assert!(is_prime(119315717514047));
+        assert!(is_prime(18446744073709551557));
+        assert!(!is_prime(3215031751));
+    }
+
+    #[test]
+    fn test_factorise()
+    {
+        assert_eq!(factorise(1), vec![]);
+        assert_eq!(factorise(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorise(1000000007), vec![(1000000007, 1)]);
+
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(49), vec![1, 7, 49]);
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(360).len(), 24);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::support::*;
+
+// The block-letter fonts used by the AoC puzzles.
+// Letters are at a fixed pitch - the glyph width plus
+// some blank spacing columns. Only the letters that
+// have been seen in puzzle answers are known.
+
+const SMALL_HEIGHT: usize = 6;
+const SMALL_PITCH: usize = 5;
+
+const SMALL_FONT: [(char, [&str; SMALL_HEIGHT]); 17] =
+[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const LARGE_HEIGHT: usize = 10;
+const LARGE_PITCH: usize = 8;
+
+const LARGE_FONT: [(char, [&str; LARGE_HEIGHT]); 15] =
+[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError
+{
+    UnsupportedHeight(i64),
+    UnrecognizedGlyphs
+    {
+        // The text with a '?' for each unrecognized
+        // glyph, and the (zero-based) glyph positions
+        text: String,
+        positions: Vec<usize>,
+    },
+}
+
+impl CharGrid
+{
+    pub fn ocr(&self) -> Result<String, OcrError>
+    {
+        self.ocr_with('#', '.')
+    }
+
+    pub fn ocr_with(&self, on: char, off: char) -> Result<String, OcrError>
+    {
+        // The font is chosen from the height of the grid.
+        // Any character that is not "on" or "off" means that
+        // glyph can't be recognized.
+
+        let height = self.get_height() as usize;
+
+        if height == SMALL_HEIGHT
+        {
+            self.ocr_font(on, off, SMALL_PITCH, SMALL_FONT.iter().map(|(c, rows)| (*c, &rows[..])))
+        }
+        else if height == LARGE_HEIGHT
+        {
+            self.ocr_font(on, off, LARGE_PITCH, LARGE_FONT.iter().map(|(c, rows)| (*c, &rows[..])))
+        }
+        else
+        {
+            Err(OcrError::UnsupportedHeight(self.get_height()))
+        }
+    }
+
+    fn ocr_font<'a, I>(&self, on: char, off: char, pitch: usize, font: I) -> Result<String, OcrError>
+        where I: Iterator<Item = (char, &'a [&'static str])> + Clone
+    {
+        // Grids built from points are often trimmed,
+        // so the last glyph may be missing its spacing
+
+        let width = self.get_width() as usize;
+        let num_glyphs = width.div_ceil(pitch);
+
+        let mut text = String::new();
+        let mut positions = Vec::new();
+
+        for glyph in 0..num_glyphs
+        {
+            let start_x = (glyph * pitch) as i64;
+
+            let found = font.clone()
+                .find(|(_, rows)|
+                {
+                    rows.iter().enumerate().all(|(y, row)|
+                    {
+                        (0..pitch).all(|dx|
+                        {
+                            let expected_on = row.chars().nth(dx) == Some('#');
+                            let p = Point::new(start_x + dx as i64, y as i64);
+
+                            if !self.is_point_in_bounds(&p)
+                            {
+                                !expected_on
+                            }
+                            else
+                            {
+                                let ch = self.get_char(&p);
+
+                                if expected_on { ch == on } else { ch == off }
+                            }
+                        })
+                    })
+                })
+                .map(|(c, _)| c);
+
+            match found
+            {
+                Some(c) => text.push(c),
+                None =>
+                {
+                    text.push('?');
+                    positions.push(glyph);
+                },
+            }
+        }
+
+        if positions.is_empty()
+        {
+            Ok(text)
+        }
+        else
+        {
+            Err(OcrError::UnrecognizedGlyphs { text, positions })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn render_large(text: &str) -> String
+    {
+        (0..LARGE_HEIGHT)
+            .map(|y|
+            {
+                text.chars()
+                    .map(|c|
+                    {
+                        let rows = LARGE_FONT.iter().find(|(fc, _)| *fc == c).unwrap().1;
+                        format!("{}..", rows[y])
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_ocr_small()
+    {
+        // 2022 Day 10 style - with trailing spacing
+
+        let grid = CharGrid::new_from_input(
+            "###...##..###..###..#..#..##..###....##.\n#..#.#..#.#..#.#..#.#.#..#..#.#..#....#.\n#..#.#....#..#.###..##...#..#.#..#....#.\n###..#....###..#..#.#.#..####.###.....#.\n#....#..#.#....#..#.#.#..#..#.#....#..#.\n#.....##..#....###..#..#.#..#.#.....##..",
+            '.');
+
+        assert_eq!(grid.ocr(), Ok("PCPBKAPJ".to_owned()));
+
+        // Trimmed and with other on/off chars
+
+        let grid = CharGrid::new_from_input(
+            "**** *  *\n*    *  *\n***  ****\n*    *  *\n*    *  *\n**** *  *",
+            ' ');
+
+        assert_eq!(grid.ocr_with('*', ' '), Ok("EH".to_owned()));
+        assert_eq!(grid.ocr(), Err(OcrError::UnrecognizedGlyphs { text: "??".to_owned(), positions: vec![0, 1] }));
+    }
+
+    #[test]
+    fn test_ocr_large()
+    {
+        let grid = CharGrid::new_from_input(&render_large("ABCEFGHJKLNPRXZ"), '.');
+        assert_eq!(grid.ocr(), Ok("ABCEFGHJKLNPRXZ".to_owned()));
+    }
+
+    #[test]
+    fn test_ocr_errors()
+    {
+        let mut grid = CharGrid::new_from_input(
+            ".##..###.\n#..#.#..#\n#..#.###.\n####.#..#\n#..#.#..#\n#..#.###.",
+            '.');
+
+        assert_eq!(grid.ocr(), Ok("AB".to_owned()));
+
+        grid.put_char(&Point::new(0, 0), '#');
+        assert_eq!(grid.ocr(), Err(OcrError::UnrecognizedGlyphs { text: "?B".to_owned(), positions: vec![0] }));
+
+        assert_eq!(CharGrid::new_from_fill(5, 7, '.').ocr(), Err(OcrError::UnsupportedHeight(7)));
+    }
+}
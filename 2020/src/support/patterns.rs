@@ -0,0 +1,186 @@
+use crate::support::*;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PatternOverlap
+{
+    Allow,
+    #[allow(dead_code)]
+    Disallow,
+}
+
+#[derive(Clone, Debug)]
+#[allow(dead_code)]
+pub struct PatternMatch
+{
+    // The transform maps points in the original
+    // pattern onto the matching grid points
+
+    pub offset: Point,
+    pub transform: Transform2,
+    pub points: Vec<Point>,
+}
+
+impl CharGrid
+{
+    pub fn find_pattern(&self, pattern: &CharGrid, wildcard: char, overlap: PatternOverlap) -> Vec<PatternMatch>
+    {
+        // Searches for every orientation of the pattern.
+        // Symmetric patterns only produce each distinct
+        // orientation once. Without overlaps, matches are
+        // taken greedily in reading order.
+
+        let mut orientations: Vec<(Transform2, CharGrid)> = Vec::new();
+
+        for (t, g) in pattern.all_orientations()
+        {
+            if !orientations.iter().any(|(_, existing)| *existing == g)
+            {
+                orientations.push((t, g));
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut used = HashSet::new();
+
+        for y in 0..self.get_height()
+        {
+            for x in 0..self.get_width()
+            {
+                let offset = Point::new(x, y);
+
+                for (t, g) in orientations.iter()
+                {
+                    if let Some(points) = self.match_at(g, wildcard, offset)
+                    {
+                        if overlap == PatternOverlap::Disallow
+                        {
+                            if points.iter().any(|p| used.contains(p))
+                            {
+                                continue;
+                            }
+                            used.extend(points.iter().copied());
+                        }
+
+                        let transform = t.append(&Transform2::translation(offset));
+
+                        result.push(PatternMatch { offset, transform, points });
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    fn match_at(&self, pattern: &CharGrid, wildcard: char, offset: Point) -> Option<Vec<Point>>
+    {
+        if (offset.x + pattern.get_width() > self.get_width())
+            || (offset.y + pattern.get_height() > self.get_height())
+        {
+            return None;
+        }
+
+        let mut points = Vec::new();
+
+        for p in pattern.all_points()
+        {
+            let ch = pattern.get_char(&p);
+
+            if ch != wildcard
+            {
+                let gp = offset + p;
+
+                if self.get_char(&gp) != ch
+                {
+                    return None;
+                }
+                points.push(gp);
+            }
+        }
+
+        Some(points)
+    }
+
+    pub fn find_word(&self, word: &str) -> Vec<(Point, Point)>
+    {
+        // Returns the start point and direction of each
+        // straight line occurrence, in any of the 8
+        // directions. Palindromes are found twice.
+
+        let chars = word.chars().collect::<Vec<_>>();
+
+        if chars.is_empty()
+        {
+            return Vec::new();
+        }
+
+        let mut result = Vec::new();
+
+        for start in self.all_points()
+        {
+            for dir in Point::directions_8()
+            {
+                let found = chars.iter().enumerate().all(|(i, &ch)|
+                {
+                    let p = start + (i as i64) * dir;
+                    self.is_point_in_bounds(&p) && (self.get_char(&p) == ch)
+                });
+
+                if found
+                {
+                    result.push((start, dir));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_find_word()
+    {
+        let grid = CharGrid::new_from_input("ABC\nBBB\nCBA", '.');
+
+        let found = grid.find_word("ABC");
+        assert_eq!(found.len(), 4);
+        assert!(found.contains(&(Point::new(0, 0), Point::new(1, 0))));
+        assert!(found.contains(&(Point::new(2, 2), Point::new(0, -1))));
+
+        assert_eq!(grid.find_word("BB").len(), 16);
+        assert_eq!(grid.find_word("").len(), 0);
+        assert_eq!(grid.find_word("ABCD").len(), 0);
+    }
+
+    #[test]
+    fn test_find_pattern()
+    {
+        let grid = CharGrid::new_from_input("#..#\n##.#\n.###\n....", '.');
+        let pattern = CharGrid::new_from_input("#.\n##", '.');
+
+        // An L shape has 4 distinct orientations
+
+        let all = grid.find_pattern(&pattern, '.', PatternOverlap::Allow);
+        assert_eq!(all.len(), 4);
+
+        for m in all.iter()
+        {
+            let mapped = pattern.all_points().into_iter()
+                .filter(|p| pattern.get_char(p) == '#')
+                .map(|p| m.transform.transform_point(p))
+                .collect::<HashSet<_>>();
+
+            assert_eq!(mapped, m.points.iter().copied().collect::<HashSet<_>>());
+        }
+
+        let disjoint = grid.find_pattern(&pattern, '.', PatternOverlap::Disallow);
+        assert_eq!(disjoint.len(), 2);
+        assert_eq!(disjoint[0].offset, Point::new(0, 0));
+    }
+}
@@ -0,0 +1,291 @@
+use crate::support::*;
+use std::collections::{HashSet, VecDeque};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Connectivity
+{
+    Four,
+    Eight,
+}
+
+impl Connectivity
+{
+    fn neighbours(&self, point: &Point) -> Vec<Point>
+    {
+        match self
+        {
+            Connectivity::Four => point.neighbours_4().collect(),
+            Connectivity::Eight => point.neighbours_8().collect(),
+        }
+    }
+
+    fn opposite(&self) -> Connectivity
+    {
+        match self
+        {
+            Connectivity::Four => Connectivity::Eight,
+            Connectivity::Eight => Connectivity::Four,
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct GridRegion
+{
+    pub id: usize,
+    pub ch: char,
+    pub grid_area: GridArea,
+    pub bounds: Rect,
+    pub touching: HashSet<usize>,
+    pub holes: Vec<GridArea>,
+}
+
+pub struct RegionMap
+{
+    labels: Grid<usize>,
+    regions: Vec<GridRegion>,
+}
+
+impl RegionMap
+{
+    pub fn regions(&self) -> &[GridRegion]
+    {
+        &self.regions
+    }
+
+    #[allow(dead_code)]
+    pub fn region_at(&self, point: &Point) -> Option<&GridRegion>
+    {
+        self.regions.get(*self.labels.get(point))
+    }
+}
+
+impl CharGrid
+{
+    pub fn find_regions(&self, connectivity: Connectivity) -> RegionMap
+    {
+        self.find_regions_by(connectivity, |a, b| a == b)
+    }
+
+    pub fn find_regions_by<F>(&self, connectivity: Connectivity, same_region: F) -> RegionMap
+        where F: Fn(char, char) -> bool
+    {
+        // Label every point with the index of its region.
+        // The predicate is applied to each pair of adjacent points,
+        // so a non-transitive predicate will chain through a region.
+
+        let width = self.get_width();
+        let index = |p: &Point| (p.y * width + p.x) as usize;
+
+        let mut sets = DenseUnionFind::new((width * self.get_height()) as usize);
+
+        for cur in self.all_points()
+        {
+            let cur_ch = self.get_char(&cur);
+
+            for n in connectivity.neighbours(&cur)
+            {
+                if self.is_point_in_bounds(&n)
+                    && same_region(cur_ch, self.get_char(&n))
+                {
+                    sets.union(index(&cur), index(&n));
+                }
+            }
+        }
+
+        // Regions are numbered in the reading
+        // order of their first point
+
+        let mut labels = Grid::new_from_fill(self.get_width() as usize, self.get_height() as usize, usize::MAX);
+        let mut areas = Vec::new();
+
+        for component in sets.components()
+        {
+            let id = areas.len();
+            let points = component.into_iter()
+                .map(|i| Point::new(i as i64 % width, i as i64 / width))
+                .collect::<HashSet<_>>();
+
+            for p in points.iter()
+            {
+                labels.put(p, id);
+            }
+
+            let first = points.iter().min_by_key(|p| index(p)).unwrap();
+            areas.push((self.get_char(first), GridArea { points }));
+        }
+
+        let regions = areas.into_iter()
+            .enumerate()
+            .map(|(id, (ch, grid_area))|
+            {
+                let bounds = area_bounds(&grid_area);
+
+                let touching = grid_area.points.iter()
+                    .flat_map(|p| connectivity.neighbours(p))
+                    .filter(|n| labels.is_point_in_bounds(n))
+                    .map(|n| *labels.get(&n))
+                    .filter(|&other| other != id)
+                    .collect();
+
+                let holes = find_holes(&grid_area, &bounds, connectivity.opposite());
+
+                GridRegion { id, ch, grid_area, bounds, touching, holes }
+            })
+            .collect();
+
+        RegionMap { labels, regions }
+    }
+}
+
+fn area_bounds(grid_area: &GridArea) -> Rect
+{
+    let min_x = grid_area.points.iter().map(|p| p.x).min().unwrap();
+    let max_x = grid_area.points.iter().map(|p| p.x).max().unwrap();
+    let min_y = grid_area.points.iter().map(|p| p.y).min().unwrap();
+    let max_y = grid_area.points.iter().map(|p| p.y).max().unwrap();
+
+    Rect::new_from_coords(min_x, min_y, max_x, max_y)
+}
+
+fn find_holes(grid_area: &GridArea, bounds: &Rect, background: Connectivity) -> Vec<GridArea>
+{
+    // Fill the background from a ring just outside the bounds.
+    // Anything inside the bounds that isn't reached is a hole.
+    // The background uses the opposite connectivity to the region,
+    // so e.g. the outside can't leak through a diagonal gap in a
+    // four-connected region.
+
+    let outer = Rect::new_from_coords(
+        bounds.get_min_x() - 1, bounds.get_min_y() - 1,
+        bounds.get_max_x() + 1, bounds.get_max_y() + 1);
+
+    let is_background = |p: &Point| outer.does_point_intersect(*p) && !grid_area.points.contains(p);
+
+    let mut outside = HashSet::new();
+    let mut to_check = VecDeque::new();
+
+    for x in outer.get_min_x()..=outer.get_max_x()
+    {
+        to_check.push_back(Point::new(x, outer.get_min_y()));
+        to_check.push_back(Point::new(x, outer.get_max_y()));
+    }
+    for y in outer.get_min_y()..=outer.get_max_y()
+    {
+        to_check.push_back(Point::new(outer.get_min_x(), y));
+        to_check.push_back(Point::new(outer.get_max_x(), y));
+    }
+
+    while let Some(cur) = to_check.pop_front()
+    {
+        if is_background(&cur) && outside.insert(cur)
+        {
+            to_check.extend(background.neighbours(&cur));
+        }
+    }
+
+    // Group the remaining points into separate holes
+
+    let mut remaining = (bounds.get_min_y()..=bounds.get_max_y())
+        .flat_map(|y| (bounds.get_min_x()..=bounds.get_max_x()).map(move |x| Point::new(x, y)))
+        .filter(|p| is_background(p) && !outside.contains(p))
+        .collect::<HashSet<_>>();
+
+    let mut holes = Vec::new();
+
+    while let Some(&start) = remaining.iter().next()
+    {
+        let mut points = HashSet::new();
+        let mut to_check = vec![start];
+
+        while let Some(cur) = to_check.pop()
+        {
+            if remaining.remove(&cur)
+            {
+                points.insert(cur);
+                to_check.extend(background.neighbours(&cur));
+            }
+        }
+
+        holes.push(GridArea { points });
+    }
+
+    holes
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_find_regions()
+    {
+        let grid = CharGrid::new_from_input("AAAA\nBBCD\nBBCC\nEEEC", '.');
+        let regions = grid.find_regions(Connectivity::Four);
+
+        assert_eq!(regions.regions().len(), 5);
+
+        let c = regions.region_at(&Point::new(2, 1)).unwrap();
+        assert_eq!(c.ch, 'C');
+        assert_eq!(c.grid_area.area(), 4);
+        assert_eq!(c.grid_area.perimeter(), 10);
+        assert_eq!(c.grid_area.num_edges(), 8);
+        assert_eq!(c.bounds, Rect::new_from_coords(2, 1, 3, 3));
+
+        let mut touching = c.touching.iter()
+            .map(|&id| regions.regions()[id].ch)
+            .collect::<Vec<_>>();
+        touching.sort();
+        assert_eq!(touching, vec!['A', 'B', 'D', 'E']);
+
+        assert!(regions.region_at(&Point::new(4, 0)).is_none());
+    }
+
+    #[test]
+    fn test_find_regions_holes()
+    {
+        let grid = CharGrid::new_from_input("#####\n#...#\n#.#.#\n#...#\n#####", '.');
+        let regions = grid.find_regions(Connectivity::Four);
+
+        assert_eq!(regions.regions().len(), 3);
+
+        let ring = regions.region_at(&Point::new(0, 0)).unwrap();
+        assert_eq!(ring.grid_area.area(), 16);
+        assert_eq!(ring.holes.len(), 1);
+        assert_eq!(ring.holes[0].area(), 9);
+
+        let centre = regions.region_at(&Point::new(2, 2)).unwrap();
+        assert_eq!(centre.touching.len(), 1);
+        assert_eq!(centre.holes.len(), 0);
+    }
+
+    #[test]
+    fn test_find_regions_connectivity()
+    {
+        // The bottom-right corner is only closed diagonally
+
+        let grid = CharGrid::new_from_input("#####\n#...#\n#.#.#\n#...#\n####.", '.');
+
+        let four = grid.find_regions(Connectivity::Four);
+        assert_eq!(four.regions().len(), 4);
+
+        let ring = four.region_at(&Point::new(0, 0)).unwrap();
+        assert_eq!(ring.grid_area.area(), 15);
+        assert_eq!(ring.holes.len(), 0);
+
+        let eight = grid.find_regions(Connectivity::Eight);
+        assert_eq!(eight.regions().len(), 3);
+
+        let ring = eight.region_at(&Point::new(0, 0)).unwrap();
+        assert_eq!(ring.grid_area.area(), 15);
+        assert_eq!(ring.holes.len(), 1);
+        assert_eq!(ring.holes[0].area(), 9);
+
+        // Custom equivalence - everything joins up
+
+        let all = grid.find_regions_by(Connectivity::Four, |_, _| true);
+        assert_eq!(all.regions().len(), 1);
+        assert_eq!(all.regions()[0].touching.len(), 0);
+    }
+}
@@ -0,0 +1,368 @@
+use crate::support::*;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T>
+{
+    // Only occupied points are stored - everything
+    // else is empty. The bounding rect is cached and
+    // only re-calculated after a point on the edge
+    // has been removed.
+
+    cells: HashMap<Point, T>,
+    bounds: Cell<Option<Rect>>,
+    bounds_valid: Cell<bool>,
+}
+
+impl<T> SparseGrid<T>
+{
+    pub fn new() -> Self
+    {
+        SparseGrid
+        {
+            cells: HashMap::new(),
+            bounds: Cell::new(None),
+            bounds_valid: Cell::new(true),
+        }
+    }
+
+    pub fn new_from_points<I>(points: I, value: T) -> Self
+        where I: IntoIterator<Item = Point>,
+            T: Clone
+    {
+        let mut result = SparseGrid::new();
+
+        for p in points
+        {
+            result.insert(p, value.clone());
+        }
+
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn new_from_char_grid<F>(grid: &CharGrid, func: F) -> Self
+        where F: Fn(char) -> Option<T>
+    {
+        let mut result = SparseGrid::new();
+
+        for p in grid.all_points()
+        {
+            if let Some(value) = func(grid.get_char(&p))
+            {
+                result.insert(p, value);
+            }
+        }
+
+        result
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.cells.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool
+    {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: &Point) -> bool
+    {
+        self.cells.contains_key(point)
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, point: &Point) -> Option<&T>
+    {
+        self.cells.get(point)
+    }
+
+    #[allow(dead_code)]
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T>
+    {
+        self.cells.get_mut(point)
+    }
+
+    pub fn get_or_insert_with<F>(&mut self, point: Point, func: F) -> &mut T
+        where F: FnOnce() -> T
+    {
+        if !self.cells.contains_key(&point)
+        {
+            self.expand_bounds(point);
+        }
+
+        self.cells.entry(point).or_insert_with(func)
+    }
+
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T>
+    {
+        self.expand_bounds(point);
+        self.cells.insert(point, value)
+    }
+
+    #[allow(dead_code)]
+    pub fn remove(&mut self, point: &Point) -> Option<T>
+    {
+        let result = self.cells.remove(point);
+
+        if result.is_some() && self.bounds_valid.get()
+        {
+            if let Some(bounds) = self.bounds.get()
+            {
+                if point.x == bounds.get_min_x()
+                    || point.x == bounds.get_max_x()
+                    || point.y == bounds.get_min_y()
+                    || point.y == bounds.get_max_y()
+                {
+                    self.bounds_valid.set(false);
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_
+    {
+        self.cells.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)>
+    {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    pub fn get_bounds(&self) -> Option<Rect>
+    {
+        if !self.bounds_valid.get()
+        {
+            let min_x = self.cells.keys().map(|p| p.x).min();
+            let max_x = self.cells.keys().map(|p| p.x).max();
+            let min_y = self.cells.keys().map(|p| p.y).min();
+            let max_y = self.cells.keys().map(|p| p.y).max();
+
+            self.bounds.set(match (min_x, min_y, max_x, max_y)
+            {
+                (Some(min_x), Some(min_y), Some(max_x), Some(max_y)) => Some(Rect::new_from_coords(min_x, min_y, max_x, max_y)),
+                _ => None,
+            });
+            self.bounds_valid.set(true);
+        }
+
+        self.bounds.get()
+    }
+
+    pub fn num_empty_in_bounds(&self) -> usize
+    {
+        match self.get_bounds()
+        {
+            None => 0,
+            Some(bounds) =>
+            {
+                let width = bounds.get_max_x() - bounds.get_min_x() + 1;
+                let height = bounds.get_max_y() - bounds.get_min_y() + 1;
+
+                (width * height) as usize - self.cells.len()
+            },
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours_4(&self, point: &Point) -> impl Iterator<Item = (Point, &T)>
+    {
+        point.neighbours_4()
+            .filter_map(move |n| self.cells.get(&n).map(|v| (n, v)))
+    }
+
+    pub fn neighbours_8(&self, point: &Point) -> impl Iterator<Item = (Point, &T)>
+    {
+        point.neighbours_8()
+            .filter_map(move |n| self.cells.get(&n).map(|v| (n, v)))
+    }
+
+    #[allow(dead_code)]
+    pub fn region(&self, rect: &Rect) -> impl Iterator<Item = (Point, &T)>
+    {
+        // Iterates the occupied points within the (inclusive) rect.
+        // Choose to scan the rect or the cells, whichever is smaller.
+
+        let rect = *rect;
+        let width = rect.get_max_x() - rect.get_min_x() + 1;
+        let height = rect.get_max_y() - rect.get_min_y() + 1;
+
+        let result: Vec<(Point, &T)> = if (width * height) < (self.cells.len() as i64)
+        {
+            (rect.get_min_y()..=rect.get_max_y())
+                .flat_map(|y| (rect.get_min_x()..=rect.get_max_x()).map(move |x| Point::new(x, y)))
+                .filter_map(|p| self.cells.get(&p).map(|v| (p, v)))
+                .collect()
+        }
+        else
+        {
+            self.cells.iter()
+                .filter(|(p, _)| rect.does_point_intersect(**p))
+                .map(|(p, v)| (*p, v))
+                .collect()
+        };
+
+        result.into_iter()
+    }
+
+    pub fn to_char_grid<F>(&self, empty: char, func: F) -> CharGrid
+        where F: Fn(&T) -> char
+    {
+        // The top-left of the resulting grid is the
+        // minimum point of the bounds - see get_origin()
+
+        let bounds = self.get_bounds().unwrap_or(Rect::new_from_coords(0, 0, 0, 0));
+
+        self.to_char_grid_in_rect(&bounds, empty, func)
+    }
+
+    pub fn to_char_grid_in_rect<F>(&self, rect: &Rect, empty: char, func: F) -> CharGrid
+        where F: Fn(&T) -> char
+    {
+        let origin = Point::new(rect.get_min_x(), rect.get_min_y());
+        let width = rect.get_max_x() - rect.get_min_x() + 1;
+        let height = rect.get_max_y() - rect.get_min_y() + 1;
+
+        let mut result = CharGrid::new_from_fill(width as usize, height as usize, empty);
+
+        for (p, v) in self.cells.iter()
+        {
+            result.put_char(&(*p - origin), func(v));
+        }
+
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn get_origin(&self) -> Point
+    {
+        self.get_bounds()
+            .map(|b| Point::new(b.get_min_x(), b.get_min_y()))
+            .unwrap_or(Point::new(0, 0))
+    }
+
+    #[allow(dead_code)]
+    pub fn render<F>(&self, empty: char, func: F) -> String
+        where F: Fn(&T) -> char
+    {
+        self.to_char_grid(empty, func).to_string()
+    }
+
+    fn expand_bounds(&mut self, point: Point)
+    {
+        if self.bounds_valid.get()
+        {
+            self.bounds.set(match self.bounds.get()
+            {
+                None => Some(Rect::new(point, point)),
+                Some(bounds) => Some(Rect::new_from_coords(
+                    bounds.get_min_x().min(point.x),
+                    bounds.get_min_y().min(point.y),
+                    bounds.get_max_x().max(point.x),
+                    bounds.get_max_y().max(point.y))),
+            });
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for SparseGrid<T>
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T>
+{
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T>
+{
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self
+    {
+        let mut result = SparseGrid::new();
+
+        for (p, v) in iter
+        {
+            result.insert(p, v);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_sparse_grid_bounds()
+    {
+        let mut grid = SparseGrid::new();
+
+        assert_eq!(grid.get_bounds(), None);
+        assert_eq!(grid.num_empty_in_bounds(), 0);
+
+        grid.insert(Point::new(3, 4), 'a');
+        assert_eq!(grid.get_bounds(), Some(Rect::new_from_coords(3, 4, 3, 4)));
+
+        grid.insert(Point::new(-2, 6), 'b');
+        grid.insert(Point::new(0, 5), 'c');
+        assert_eq!(grid.get_bounds(), Some(Rect::new_from_coords(-2, 4, 3, 6)));
+        assert_eq!(grid.num_empty_in_bounds(), 15);
+
+        grid.remove(&Point::new(0, 5));
+        assert_eq!(grid.get_bounds(), Some(Rect::new_from_coords(-2, 4, 3, 6)));
+
+        grid.remove(&Point::new(-2, 6));
+        assert_eq!(grid.get_bounds(), Some(Rect::new_from_coords(3, 4, 3, 4)));
+
+        grid.remove(&Point::new(3, 4));
+        assert_eq!(grid.get_bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_sparse_grid_neighbours_and_region()
+    {
+        let grid = SparseGrid::new_from_char_grid(
+            &CharGrid::new_from_input("#.#\n.##\n...", '.'),
+            |c| if c == '#' { Some(c) } else { None });
+
+        assert_eq!(grid.len(), 4);
+        assert_eq!(grid.neighbours_4(&Point::new(1, 1)).count(), 1);
+        assert_eq!(grid.neighbours_8(&Point::new(1, 1)).count(), 3);
+        assert_eq!(grid.neighbours_8(&Point::new(5, 5)).count(), 0);
+        assert_eq!(grid.region(&Rect::new_from_coords(1, 0, 2, 1)).count(), 3);
+        assert_eq!(grid.region(&Rect::new_from_coords(2, 2, 2, 2)).count(), 0);
+    }
+
+    #[test]
+    fn test_sparse_grid_render()
+    {
+        let grid = SparseGrid::new_from_points(
+            vec![Point::new(-1, -1), Point::new(1, 0), Point::new(0, 1)],
+            '#');
+
+        assert_eq!(grid.get_origin(), Point::new(-1, -1));
+        assert_eq!(grid.render('.', |&c| c), "#..\n..#\n.#.");
+        assert_eq!(
+            grid.to_char_grid_in_rect(&Rect::new_from_coords(0, 0, 2, 1), ' ', |&c| c).to_string(),
+            " # \n#  ");
+
+        let mut counts = SparseGrid::new();
+        *counts.get_or_insert_with(Point::new(5, 5), || 0) += 1;
+        *counts.get_or_insert_with(Point::new(5, 5), || 0) += 1;
+        *counts.get_or_insert_with(Point::new(6, 5), || 0) += 1;
+        assert_eq!(counts.render('.', |&c| std::char::from_digit(c, 10).unwrap()), "21");
+    }
+}
@@ -1,68 +1,19 @@
 use crate::support::*;
-use std::collections::{HashMap, HashSet};
-use std::cmp::{min, max};
+use std::collections::HashMap;
 
 const EXAMPLE1: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Point
-{
-    x: u32,
-    y: u32,
-}
-
-#[derive(Debug)]
-struct Rectangle
-{
-    location: Point,
-    size: Point,
-}
-
-impl Rectangle
-{
-    fn overlap(&self, other: &Rectangle) -> Option<Rectangle>
-    {
-        let min_x = max(self.location.x, other.location.x);
-        let min_y = max(self.location.y, other.location.y);
-
-        let max_x = min(self.location.x + self.size.x, other.location.x + other.size.x);
-        let max_y = min(self.location.y + self.size.y, other.location.y + other.size.y);
-
-        if (min_x < max_x) && (min_y < max_y)
-        {
-            return Some(Rectangle{
-                location: Point{ x: min_x, y: min_y },
-                size: Point{ x: max_x - min_x, y: max_y - min_y },
-            })
-        }
-        return None;
-    }
-
-    fn points(&self) -> HashSet<Point>
-    {
-        let mut result = HashSet::new();
-        for x in 0..self.size.x
-        {
-            for y in 0..self.size.y
-            {
-                result.insert(Point{x: self.location.x + x, y: self.location.y + y});
-            }
-        }
-        return result;
-    }
-}
-
-fn parse_point(input: &str, split: &str) -> Point
+fn parse_point(input: &str, split: &str) -> (i64, i64)
 {
     let parts = input.split(split).collect::<Vec<_>>();
 
-    let x = parts[0].parse::<u32>().unwrap();
-    let y = parts[1].parse::<u32>().unwrap();
+    let x = parts[0].parse::<i64>().unwrap();
+    let y = parts[1].parse::<i64>().unwrap();
 
-    Point{x, y}
+    (x, y)
 }
 
-fn load(input: &str) -> HashMap<u32, Rectangle>
+fn load(input: &str) -> HashMap<u32, Rect>
 {
     let mut result = HashMap::new();
 
@@ -71,67 +22,53 @@ fn load(input: &str) -> HashMap<u32, Rectangle>
         let parts = line.split(" ").collect::<Vec<_>>();
 
         let id = parts[0][1..].parse::<u32>().unwrap();
-        let location = parse_point(&parts[2][..(parts[2].len()-1)], ",");
-        let size = parse_point(&parts[3], "x");
+        let (x, y) = parse_point(&parts[2][..(parts[2].len()-1)], ",");
+        let (w, h) = parse_point(parts[3], "x");
 
-        result.insert(id, Rectangle{location, size});
+        result.insert(id, Rect::new_from_coords(x, y, x + w - 1, y + h - 1));
     }
 
-    return result;
+    result
 }
 
-fn part_1(input: &str) -> usize
+fn find_overlaps(rects: &HashMap<u32, Rect>) -> RectSet
 {
-    let rects = load(input);
-    let mut overlapping = HashSet::new();
+    let mut covered = RectSet::new();
+    let mut overlapping = RectSet::new();
 
-    for (ida, recta) in rects.iter()
+    for rect in rects.values()
     {
-        for (idb, rectb) in rects.iter()
-        {
-            if ida != idb
-            {
-                if let Some(overlap) = recta.overlap(&rectb)
-                {
-                    for point in overlap.points()
-                    {
-                        overlapping.insert(point);
-                    }
-                }
-            }
-        }
+        let mut common = covered.clone();
+        common.intersect_with_rect(*rect);
+
+        overlapping.insert_set(&common);
+        covered.insert_rect(*rect);
     }
-    
-    return overlapping.len();
+
+    overlapping
+}
+
+fn part_1(input: &str) -> i64
+{
+    find_overlaps(&load(input)).area()
 }
 
 fn part_2(input: &str) -> u32
 {
     let rects = load(input);
+    let overlapping = find_overlaps(&rects);
 
-    for (ida, recta) in rects.iter()
+    for (id, rect) in rects.iter()
     {
-        let mut overlapped = false;
-
-        for (idb, rectb) in rects.iter()
-        {
-            if ida != idb
-            {
-                if recta.overlap(&rectb).is_some()
-                {
-                    overlapped = true;
-                    break;
-                }
-            }
-        }
+        let mut common = overlapping.clone();
+        common.intersect_with_rect(*rect);
 
-        if !overlapped
+        if common.is_empty()
         {
-            return *ida;
+            return *id;
         }
     }
 
-    assert!(false);
     unreachable!();
 }
 
@@ -1,16 +1,5 @@
 use crate::support::*;
 
-//  **    ** **** *    ***
-// *  *    *    * *    *  *
-// *       *   *  *    *  *
-// *       *  *   *    ***
-// *  * *  * *    *    *
-//  **   **  **** **** *
-//
-// i.e. CJZLP
-
-const PART_2_ANSWER: &str = " **    ** **** *    ***  \n*  *    *    * *    *  * \n*       *   *  *    *  * \n*       *  *   *    ***  \n*  * *  * *    *    *    \n **   **  **** **** *    \n";
-
 #[derive(Debug)]
 pub struct PaintPoint
 {
@@ -144,7 +133,9 @@ fn part_1(input: &str) -> usize
 
 fn part_2(input: &str) -> String
 {
-    render_image(input, 25, 6)
+    CharGrid::new_from_input(&render_image(input, 25, 6), ' ')
+        .ocr_with('*', ' ')
+        .unwrap()
 }
 
 pub fn puzzles() -> PuzzleDay
@@ -164,6 +155,6 @@ pub fn puzzles() -> PuzzleDay
         })
         .part_2(|input| Answer {
             calculated: part_2(input),
-            expected: PART_2_ANSWER,
+            expected: "CJZLP",
         })
 }
@@ -2,18 +2,6 @@ use std::collections::HashMap;
 use crate::support::*;
 use crate::y2019::intcode::*;
 
-// .##..#..#..##..#..#.####.####.###..#..#
-// #..#.#..#.#..#.#..#....#.#....#..#.#.#.
-// #..#.####.#....####...#..###..#..#.##..
-// ####.#..#.#....#..#..#...#....###..#.#.
-// #..#.#..#.#..#.#..#.#....#....#....#.#.
-// #..#.#..#..##..#..#.####.####.#....#..#
-//
-// i.e. AHCHZEPK
-
-const PART_2_ANSWER: &str = ".##..#..#..##..#..#.####.####.###..#..#\n#..#.#..#.#..#.#..#....#.#....#..#.#.#.\n#..#.####.#....####...#..###..#..#.##..\n####.#..#.#....#..#..#...#....###..#.#.\n#..#.#..#.#..#.#..#.#....#....#....#.#.\n#..#.#..#..##..#..#.####.####.#....#..#";
-
-
 fn run(input: &str, start_on_white: bool) -> HashMap::<Point, bool>
 {
     let mut comp = Intcode::new_from_input(input);
@@ -80,12 +68,12 @@ fn part_2(input: &str) -> String
         .map(|(pos, _)| Point::new(pos.x, -pos.y))
         .collect::<Vec<Point>>();
 
-    CharGrid::new_from_points(points).to_string()
+    CharGrid::new_from_points(points).ocr().unwrap()
 }
 
 pub fn puzzles() -> PuzzleDay
 {
     puzzle_day(11)
         .part_1(|input| Answer { calculated: part_1(input), expected: 2184, })
-        .part_2(|input| Answer { calculated: part_2(input), expected: PART_2_ANSWER, })
+        .part_2(|input| Answer { calculated: part_2(input), expected: "AHCHZEPK", })
 }
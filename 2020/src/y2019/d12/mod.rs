@@ -1,5 +1,4 @@
 use crate::support::*;
-use std::collections::HashSet;
 
 const EXAMPLE_1: &str = include_str!("example_1.txt");
 const EXAMPLE_2: &str = include_str!("example_2.txt");
@@ -115,9 +114,12 @@ fn part_1(input: &str, steps: usize) -> i64
     system.energy()
 }
 
-fn count_until_coordinate_repeats<F>(input: &str, get_coord: F) -> usize
+fn coordinate_period<F>(input: &str, get_coord: F) -> usize
     where F: Fn(&Point3) -> i64
 {
+    // Each axis is independent, so find
+    // how often each one repeats
+
     let system_to_state = |system: &System| -> Vec<(i64, i64)>
     {
         system.moons.iter()
@@ -125,27 +127,14 @@ fn count_until_coordinate_repeats<F>(input: &str, get_coord: F) -> usize
             .collect()
     };
 
-    let mut system = System::new(input);
-    let mut count = 0;
-    let mut seen = HashSet::new();
-
-    loop
-    {
-        if !seen.insert(system_to_state(&system))
-        {
-            return count;
-        }
-
-        system.one_step();
-        count += 1;
-    }
+    find_cycle_brent(&System::new(input), |s| s.one_step(), system_to_state).length
 }
 
 fn part_2(input: &str) -> u64
 {
-    let x_repeat = count_until_coordinate_repeats(input, |p| p.x);
-    let y_repeat = count_until_coordinate_repeats(input, |p| p.y);
-    let z_repeat = count_until_coordinate_repeats(input, |p| p.z);
+    let x_repeat = coordinate_period(input, |p| p.x);
+    let y_repeat = coordinate_period(input, |p| p.y);
+    let z_repeat = coordinate_period(input, |p| p.z);
 
     lcm(lcm(x_repeat as u64, y_repeat as u64), z_repeat as u64)
 }
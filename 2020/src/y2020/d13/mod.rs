@@ -27,37 +27,17 @@ fn part_1(input: &str) -> u64
     }
 }
 
-fn combine_new_bus(first_ts: u64, period: u64, bus_id: u64, index: u64) -> (u64, u64)
-{
-    let mut cur_ts = first_ts;
-    loop
-    {
-        if ((cur_ts + index) % bus_id) == 0
-        {
-            return (cur_ts, lcm(period, bus_id));
-        }
-        cur_ts += period;
-    }
-}
-
 fn part_2_ans(line: &str) -> u64
 {
-    let ids = line.split(",").map(|i| if i == "x" { None } else { Some(i.parse::<u64>().unwrap()) }).collect::<Vec<Option<u64>>>();
-
-    let mut first_ts = 0;
-    let mut period = 1;
+    // The bus at index i leaves at ts + i, so
+    // we need ts = -i (mod bus id) for every bus
 
-    for i in 0..ids.len()
-    {
-        if ids[i].is_some()
-        {
-            let combined = combine_new_bus(first_ts, period, ids[i].unwrap(), i as u64);
-            first_ts = combined.0;
-            period = combined.1;
-        }
-    }
+    let congruences = line.split(",")
+        .enumerate()
+        .filter(|(_, id)| *id != "x")
+        .map(|(i, id)| (-(i as i64), id.parse::<i64>().unwrap()));
 
-    first_ts
+    chinese_remainder(congruences).unwrap().0 as u64
 }
 
 fn part_2(input: &str) -> u64
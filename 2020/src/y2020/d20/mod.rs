@@ -1,8 +1,14 @@
 use crate::support::*;
+use std::collections::HashSet;
 
 const EXAMPLE: &str = include_str!("example.txt");
 const EXAMPLE_REASSEMBLED: &str = include_str!("example_reassembled.txt");
 
+const SEA_MONSTER: &str = "\
+..................#.
+#....##....##....###
+.#..#..#..#..#..#...";
+
 #[derive(Clone)]
 struct Tile
 {
@@ -59,18 +65,10 @@ fn borders_x8(grid: &CharGrid) -> Vec<String>
 
 fn grid_variants(grid: &CharGrid) -> Vec<CharGrid>
 {
-    let mut result = Vec::new();
-
-    let mut g = grid.clone();
-
-    for _ in 0..4
-    {
-        result.push(g.clone());
-        result.push(g.flip_horizontally());
-        g = g.rotate_cw_90();
-    }
-
-    result
+    grid.all_orientations()
+        .into_iter()
+        .map(|(_, g)| g)
+        .collect()
 }
 
 fn corner_tiles(input: &str) -> Vec<Tile>
@@ -189,16 +187,15 @@ fn build_full_image(input: &str) -> CharGrid
         .next()
         .unwrap();
 
-    let mut result = CharGrid::new_from_fill(
-        (8 * num_tiles_per_side) as usize,
-        (8 * num_tiles_per_side) as usize,
-        '.');
+    let mut rows = Vec::new();
 
     let mut prev_tile = (first_corner_num, first_corner.clone());
     let mut prev_row_start = (first_corner_num, first_corner.clone());
 
     for y in 0..num_tiles_per_side
     {
+        let mut row = Vec::new();
+
         for x in 0..num_tiles_per_side
         {
             let insert = if y == 0 && x == 0
@@ -223,21 +220,13 @@ fn build_full_image(input: &str) -> CharGrid
                 prev_row_start = insert.clone();
             }
 
-            for iy in 0..8
-            {
-                for ix in 0..8
-                {
-                    result.put_char(
-                        &Point::new(8 * x + ix, 8 * y + iy),
-                        insert.1.get_char(&Point::new(ix + 1, iy + 1)));
-                }
-            }
-
-            //println!("{}", result.to_string());
+            row.push(insert.1.sub_grid(Rect::new_from_coords(1, 1, 8, 8)));
         }
+
+        rows.push(row);
     }
 
-    result
+    CharGrid::new_from_tiles(&rows)
 }
 
 fn check_reassembly(input: &str, expected: &str) -> bool
@@ -251,58 +240,6 @@ fn check_reassembly(input: &str, expected: &str) -> bool
         .count() != 0
 }
 
-fn mark_sea_monsters(grid: &mut CharGrid) -> bool
-{
-    let mut result = false;
-
-    let matches = |ch: char| -> bool
-    {
-        ch == '#' || ch == 'O'
-    };
-
-    let points = vec![
-        Point::new(0, 1),
-        Point::new(1, 2),
-        Point::new(4, 2),
-        Point::new(5, 1),
-        Point::new(6, 1),
-        Point::new(7, 2),
-        Point::new(10, 2),
-        Point::new(11, 1),
-        Point::new(12, 1),
-        Point::new(13, 2),
-        Point::new(16, 2),
-        Point::new(17, 1),
-        Point::new(18, 1),
-        Point::new(18, 0),
-        Point::new(19, 1),
-    ];
-    let req = points.len();
-
-    for y in 0..(grid.get_height() - 1)
-    {
-        for x in 0..(grid.get_width() - 18)
-        {
-            if points
-                .iter()
-                .filter(|p| matches(grid.get_char(&Point::new(x + p.x, y + p.y))))
-                .count() == req
-            {
-                // Found one - mark it
-
-                result = true;
-
-                for p in points.iter()
-                {
-                    grid.put_char(&Point::new(x + p.x, y + p.y), 'O');
-                }
-            }
-        }
-    }
-
-    result
-}
-
 fn part_1(input: &str) -> u64
 {
     corner_tiles(input)
@@ -313,20 +250,20 @@ fn part_1(input: &str) -> u64
 
 fn part_2(input: &str) -> usize
 {
-    for mut grid in grid_variants(&build_full_image(input))
-    {
-        if mark_sea_monsters(&mut grid)
-        {
-            //println!("{}", grid.to_string());
+    let image = build_full_image(input);
+    let sea_monster = CharGrid::new_from_input(SEA_MONSTER, '.');
 
-            return grid
-                .all_chars()
-                .into_iter()
-                .filter(|&ch| ch == '#').count();
-        }
-    }
+    let monster_points = image.find_pattern(&sea_monster, '.', PatternOverlap::Allow)
+        .into_iter()
+        .flat_map(|m| m.points)
+        .collect::<HashSet<_>>();
 
-    unreachable!();
+    assert!(!monster_points.is_empty());
+
+    image.all_chars()
+        .into_iter()
+        .filter(|&ch| ch == '#')
+        .count() - monster_points.len()
 }
 
 pub fn puzzles() -> PuzzleDay
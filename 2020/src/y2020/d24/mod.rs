@@ -3,60 +3,14 @@ use crate::support::*;
 
 const EXAMPLE: &str = include_str!("example.txt");
 
-fn parse_dirs(input: &str) -> Vec<Point>
-{
-    let mut result = Vec::new();
-    let mut it = input.chars();
-
-    loop
-    {
-        match it.next()
-        {
-            None => break,
-            Some(ch) =>
-            {
-                match ch
-                {
-                    'e' => result.push(Point::new(2, 0)),
-                    'w' => result.push(Point::new(-2, 0)),
-                    'n' =>
-                    {
-                        match it.next().unwrap()
-                        {
-                            'e' => result.push(Point::new(1, 1)),
-                            'w' => result.push(Point::new(-1, 1)),
-                            _ => unreachable!(),
-                        }
-                    },
-                    's' =>
-                    {
-                        match it.next().unwrap()
-                        {
-                            'e' => result.push(Point::new(1, -1)),
-                            'w' => result.push(Point::new(-1, -1)),
-                            _ => unreachable!(),
-                        }
-                    },
-                    _ => unreachable!(),
-                };
-            },
-        }
-    }
-
-    result
-}
-
-fn flip_tiles(input: &str) -> HashSet<Point>
+fn flip_tiles(input: &str) -> HashSet<HexPoint>
 {
     let mut result = HashSet::new();
 
     for line in input_to_lines(input)
     {
-        let mut pos = Point::new(0, 0);
-        for p in parse_dirs(&line)
-        {
-            pos += p;
-        }
+        let pos = HexOrientation::PointyTop.parse_directions(&line).into_iter()
+            .fold(HexPoint::origin(), |pos, dir| pos + dir);
 
         if result.contains(&pos)
         {
@@ -71,18 +25,6 @@ fn flip_tiles(input: &str) -> HashSet<Point>
     result
 }
 
-fn neighbours(pos: &Point) -> Vec<Point>
-{
-    let mut result = Vec::new();
-    result.push(Point::new(pos.x + 2, pos.y));
-    result.push(Point::new(pos.x - 2, pos.y));
-    result.push(Point::new(pos.x + 1, pos.y + 1));
-    result.push(Point::new(pos.x - 1, pos.y + 1));
-    result.push(Point::new(pos.x + 1, pos.y - 1));
-    result.push(Point::new(pos.x - 1, pos.y - 1));
-    result
-}
-
 fn part_1(input: &str) -> usize
 {
     flip_tiles(input).len()
@@ -97,7 +39,7 @@ fn part_2(input: &str) -> usize
         let mut points_to_test = HashSet::new();
         for p in state.iter()
         {
-            for n in neighbours(p)
+            for n in p.neighbours()
             {
                 points_to_test.insert(n);
             }
@@ -108,31 +50,17 @@ fn part_2(input: &str) -> usize
         for p in points_to_test
         {
             let cur_black = state.contains(&p);
-            let black_neighbours = neighbours(&p).into_iter()
+            let black_neighbours = p.neighbours()
                 .filter(|n| state.contains(n))
                 .count();
 
             let new_black = if cur_black
             {
-                if black_neighbours == 0 || black_neighbours > 2
-                {
-                    false
-                }
-                else
-                {
-                    true
-                }
+                black_neighbours == 1 || black_neighbours == 2
             }
             else // currently white
             {
-                if black_neighbours == 2
-                {
-                    true
-                }
-                else
-                {
-                    false
-                }
+                black_neighbours == 2
             };
 
             if new_black
@@ -1,5 +1,4 @@
 use crate::support::*;
-use std::collections::HashMap;
 
 const EXAMPLE: &str = include_str!("example.txt");
 
@@ -7,7 +6,7 @@ fn solve(input: &str, diagonals: bool) -> usize
 {
     let lines = input_to_lines(input);
 
-    let mut point_to_count: HashMap<Point, usize> = HashMap::new();
+    let mut point_to_count: SparseGrid<usize> = SparseGrid::new();
 
     for line in lines.iter()
     {
@@ -26,20 +25,13 @@ fn solve(input: &str, diagonals: bool) -> usize
         {
             for point in line.points_exactly_on_line_inclusive()
             {
-                if let Some(count) = point_to_count.get_mut(&point)
-                {
-                    *count += 1;
-                }
-                else
-                {
-                    point_to_count.insert(point, 1);
-                }
+                *point_to_count.get_or_insert_with(point, || 0) += 1;
             }
         }
     }
 
     point_to_count.iter()
-        .filter(|entry| *entry.1 >= 2)
+        .filter(|(_, count)| **count >= 2)
         .count()
 }
 
@@ -5,7 +5,7 @@ const EXAMPLE: &str = include_str!("example.txt");
 
 struct OctoGrid
 {
-    cells: CharGrid,
+    cells: Grid<u8>,
     num_flashes: usize,
 }
 
@@ -15,7 +15,7 @@ impl OctoGrid
     {
         OctoGrid
         {
-            cells: CharGrid::new_from_input(input, '0'),
+            cells: Grid::new_from_input(input, '0', |c| c.to_digit(10).unwrap() as u8),
             num_flashes: 0,
         }
     }
@@ -40,9 +40,8 @@ impl OctoGrid
         {
             let p = to_inc.pop_front().unwrap();
 
-            let mut c = self.cells.get_char(&p);
-            c = ((c as u8) + 1) as char;
-            if c > '9'
+            let c = *self.cells.get(&p) + 1;
+            if c > 9
             {
                 if flashed.insert(p.clone())
                 {
@@ -53,7 +52,7 @@ impl OctoGrid
                 }
             }
 
-            self.cells.put_char(&p, c);
+            self.cells.put(&p, c);
         }
 
         let result = flashed.len();
@@ -61,7 +60,7 @@ impl OctoGrid
 
         for p in flashed
         {
-            self.cells.put_char(&p, '0');
+            self.cells.put(&p, 0);
         }
 
         result
@@ -5,8 +5,6 @@ const EXAMPLE: &str = include_str!("example.txt");
 
 // A square box
 const PART_2_ANSWER_FOR_EXAMPLE: &str = "#####\n#...#\n#...#\n#...#\n#####";
-// "HKUJGAJZ"
-const PART_2_ANSWER_FOR_INPUT: &str = "#..#.#..#.#..#...##..##...##....##.####\n#..#.#.#..#..#....#.#..#.#..#....#....#\n####.##...#..#....#.#....#..#....#...#.\n#..#.#.#..#..#....#.#.##.####....#..#..\n#..#.#.#..#..#.#..#.#..#.#..#.#..#.#...\n#..#.#..#..##...##...###.#..#..##..####";
 
 enum Fold
 {
@@ -81,7 +79,7 @@ fn part_1(input: &str) -> usize
     set.len()
 }
 
-fn part_2(input: &str) -> String
+fn part_2(input: &str) -> CharGrid
 {
     let input = parse(input);
 
@@ -94,7 +92,7 @@ fn part_2(input: &str) -> String
                 .fold(p, |p, f| f.apply(p)));
     }
 
-    CharGrid::new_from_points(set.drain().collect()).to_string()
+    CharGrid::new_from_points(set.drain().collect())
 }
 
 pub fn puzzles() -> PuzzleDay
@@ -109,11 +107,11 @@ pub fn puzzles() -> PuzzleDay
             expected: 621,
         })
         .example(|| Answer {
-            calculated: part_2(EXAMPLE),
+            calculated: part_2(EXAMPLE).to_string(),
             expected: PART_2_ANSWER_FOR_EXAMPLE,
         })
         .part_2(|input| Answer {
-            calculated: part_2(input),
-            expected: PART_2_ANSWER_FOR_INPUT,
+            calculated: part_2(input).ocr().unwrap(),
+            expected: "HKUJGAJZ",
         })
 }
@@ -29,43 +29,21 @@ fn parse(input: &str) -> Vec<(bool, Cuboid)>
 
 fn points_after_operations(ops: Vec<(bool, Cuboid)>) -> i64
 {
-    let mut on_regions: Vec<Cuboid> = Vec::new();
+    let mut on_regions = CuboidSet::new();
 
     for (on, region) in ops
     {
-        let mut new_regions = Vec::new();
-
-        // Always subtract this new region from
-        // any existing regions
-        // a) When turning on lights - so we don't count
-        //    a single location more than once.
-        // b) When turning off lights - so they are
-        //    not counted as on any more.
-
-        for existing in on_regions
+        if on
         {
-            if let Some(diff) = existing.difference(region)
-            {
-                for d in diff
-                {
-                    new_regions.push(d);
-                }
-            }
+            on_regions.insert_cuboid(region);
         }
-
-        // If turning on - add this region
-
-        if on
+        else
         {
-            new_regions.push(region);
+            on_regions.remove_cuboid(region);
         }
-
-        on_regions = new_regions;
     }
 
-    on_regions.iter()
-        .map(|c| c.volume())
-        .sum()
+    on_regions.volume()
 }
 
 fn part_1(input: &str) -> i64
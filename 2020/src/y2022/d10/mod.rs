@@ -111,12 +111,12 @@ fn part_1(input: &str) -> i64
         .sum()
 }
 
-fn part_2(input: &str) -> String
+fn part_2(input: &str) -> CharGrid
 {
     let mut device = CommsDevice::new(input);
     device.run();
 
-    device.image.to_string()
+    device.image
 }
 
 pub fn puzzles() -> PuzzleDay
@@ -131,11 +131,11 @@ pub fn puzzles() -> PuzzleDay
             expected: 14320,
         })
         .example(|| Answer {
-            calculated: part_2(EXAMPLE),
+            calculated: part_2(EXAMPLE).to_string(),
             expected: "##..##..##..##..##..##..##..##..##..##..\n###...###...###...###...###...###...###.\n####....####....####....####....####....\n#####.....#####.....#####.....#####.....\n######......######......######......####\n#######.......#######.......#######.....",
         })
         .part_2(|input| Answer {
-            calculated: part_2(input),
-            expected: "###...##..###..###..#..#..##..###....##.\n#..#.#..#.#..#.#..#.#.#..#..#.#..#....#.\n#..#.#....#..#.###..##...#..#.#..#....#.\n###..#....###..#..#.#.#..####.###.....#.\n#....#..#.#....#..#.#.#..#..#.#....#..#.\n#.....##..#....###..#..#.#..#.#.....##..",
+            calculated: part_2(input).ocr().unwrap(),
+            expected: "PCPBKAPJ",
         })
 }
@@ -1,6 +1,4 @@
 use crate::support::*;
-use itertools::*;
-use pathfinding::directed::astar::*;
 
 const EXAMPLE: &str = include_str!("example.txt");
 
@@ -32,25 +30,20 @@ fn end(area: &CharGrid) -> Point
 fn steps_to<F>(area: &CharGrid, success: F) -> usize
     where F: Fn(&Point) -> bool
 {
-    let end_point = end(&area);
+    // Search backwards from the end - so we can
+    // step down at most one, or up any amount
 
-    let search_result = astar(
-        &end_point,
-        |p|
-        {
-            let p_height = height(area.get_char(p));
-            p.neighbours_4()
-                .filter(|n| height(area.get_char(n)) >= (p_height - 1))
-                .map(|n| (n, 1))
-                .collect_vec()
-        },
-        |p|
-        {
-            height(area.get_char(p))
-        },
-        success);
+    let end_point = end(area);
 
-    return search_result.unwrap().0.len() - 1;
+    let distances = bfs_distance_map_with_steps(
+        std::iter::once(end_point),
+        |from, to| height(area.get_char(to)) >= (height(area.get_char(from)) - 1));
+
+    distances.into_iter()
+        .filter(|(p, _)| success(p))
+        .map(|(_, d)| d)
+        .min()
+        .unwrap()
 }
 
 fn part_1(input: &str) -> usize
@@ -1,37 +1,14 @@
+use itertools::Itertools;
 use crate::support::*;
 
 const EXAMPLE: &str = include_str!("example.txt");
 
 struct Datum
 {
-    sensor: Point,
+    coverage: ManhattanBall,
     nearest_beacon: Point,
 }
 
-impl Datum
-{
-    fn size(&self) -> i64
-    {
-        (self.sensor - self.nearest_beacon).manhatten_size()
-    }
-    
-
-    fn x_extent_at_y(&self, y: i64) -> Option<RangeInc<i64>>
-    {
-        let size = self.size();
-        let dist = (y - self.sensor.y).abs();
-        if dist <= size
-        {
-            let offset = size - dist;
-            Some(RangeInc::new_range(self.sensor.x - offset, self.sensor.x + offset))
-        }
-        else
-        {
-            None
-        }
-    }
-}
-
 fn parse_datum(line: &str) -> Datum
 {
     let (sx, sy, bx, by) = scan(line)
@@ -41,7 +18,10 @@ fn parse_datum(line: &str) -> Datum
         .until(", y=").parse()
         .remaining().parse();
 
-    Datum { sensor: Point::new(sx, sy), nearest_beacon: Point::new(bx, by) }
+    let sensor = Point::new(sx, sy);
+    let nearest_beacon = Point::new(bx, by);
+
+    Datum { coverage: ManhattanBall::new_from_edge(sensor, nearest_beacon), nearest_beacon }
 }
 
 fn parse(input: &str) -> Vec<Datum>
@@ -58,7 +38,7 @@ fn x_may_contain_beacon_at_y(data: &Vec<Datum>, y: i64, include_known_beacons: b
 
     for d in data
     {
-        if let Some(r) = d.x_extent_at_y(y)
+        if let Some(r) = d.coverage.x_extent_at_y(y)
         {
             result.remove_range(r);
         }
@@ -85,22 +65,57 @@ fn part_1(input: &str, y: i64) -> usize
         .count()
 }
 
+fn beacon_in_rotated_rect(rect: &Rect, size: i64) -> Option<Point>
+{
+    // Finds a real point in the rotated rect that
+    // maps back into the (un-rotated) search area
+
+    (rect.get_min_x()..=rect.get_max_x())
+        .find_map(|u|
+        {
+            // x = (u + v) / 2 and y = (u - v) / 2 must
+            // both be in 0..=size, and u and v must have
+            // the same parity
+
+            let min_v = rect.get_min_y().max(-u).max(u - 2 * size);
+            let max_v = rect.get_max_y().min(u).min(2 * size - u);
+
+            let v = if (min_v - u).rem_euclid(2) == 0 { min_v } else { min_v + 1 };
+
+            if v <= max_v
+            {
+                ManhattanBall::unrotate_point(Point::new(u, v))
+            }
+            else
+            {
+                None
+            }
+        })
+}
+
 fn part_2(input: &str, size: i64) -> i64
 {
+    // In rotated co-ordinates (u = x + y, v = x - y) each
+    // sensor's diamond becomes a square - remove them all
+    // from the rotated search area.
+    // The beacon is in one of the small gaps that are left - the
+    // large uncovered areas are outside the original search area.
+
     let data = parse(input);
 
-    for y in 0..(size + 1)
+    let mut uncovered = RectSet::new_from_rect(Rect::new_from_coords(0, -size, 2 * size, size));
+
+    for d in data.iter()
     {
-        if let Some(x) =
-            x_may_contain_beacon_at_y(&data, y, false)
-            .insersection_with_range(RangeInc::new_range(0, size+1))
-            .values()
-            .next()
-        {
-            return x * 4000000 + y;
-        }
+        uncovered.remove_rect(d.coverage.to_rotated_rect());
     }
-    unreachable!();
+
+    let beacon = uncovered.rects()
+        .sorted_by_key(|r| r.num_points())
+        .find_map(|r| beacon_in_rotated_rect(&r, size))
+        .unwrap();
+
+    beacon.x * 4000000 + beacon.y
 }
 
 pub fn puzzles() -> PuzzleDay
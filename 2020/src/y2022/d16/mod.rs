@@ -1,7 +1,6 @@
 use crate::support::*;
 use itertools::*;
 use std::collections::HashMap;
-use pathfinding::directed::astar::*;
 
 const EXAMPLE: &str = include_str!("example.txt");
 
@@ -56,38 +55,21 @@ impl Map
             .map(|l| (l.name.clone(), l))
             .collect::<HashMap<_,_>>();
 
-        let mut distances = HashMap::new();
+        // The shortest distance between every pair of
+        // locations - only small, so Floyd-Warshall
 
-        let important_node_names = locations
-            .values()
-            .filter(|l| l.name == "AA" || l.flow_rate > 0)
-            .map(|l| l.name.clone())
-            .collect_vec();
+        let mut tunnels = Graph::new_directed();
 
-        for i in 0..(important_node_names.len() - 1)
+        for l in locations.values()
         {
-            let i_name = &important_node_names[i];
-
-            for j in 0..important_node_names.len()
+            for d in l.dests.iter()
             {
-                let j_name = &important_node_names[j];
-
-                let distance = astar(
-                    i_name,
-                    |loc|
-                    {
-                        locations.get(loc).unwrap().dests
-                            .iter()
-                            .map(|d| (d.clone(), 1))
-                    },
-                    |_| 0,
-                    |loc| *loc == *j_name).unwrap().1;
-
-                distances.insert((i_name.clone(), j_name.clone()), distance);
-                distances.insert((j_name.clone(), i_name.clone()), distance);
+                tunnels.add_edge(l.name.clone(), d.clone(), 1);
             }
         }
 
+        let distances = tunnels.floyd_warshall();
+
         let valves = locations
             .values()
             .filter(|l| l.flow_rate > 0)
@@ -218,10 +200,27 @@ impl Map
         }
     }
 
+    fn most_pressure_released(&self, num_players: usize, time_to_work: i64) -> i64
+    {
+        most_pressure_released_from_loc_to_closed_valves_in_time(
+            self,
+            &MemorizedInput
+            {
+                players: vec![("AA".to_string(), time_to_work); num_players],
+                closed_valves: self.valves.clone(),
+            })
+    }
+}
+
+memorize!
+{
+    // The map doesn't change during the search, so
+    // only the players and closed valves are the key
+
+    #[key(input.clone())]
     fn most_pressure_released_from_loc_to_closed_valves_in_time(
-        &self,
-        input: &MemorizedInput,
-        recurse: &Memorized<MemorizedInput, i64>) -> i64
+        map: &Map,
+        input: &MemorizedInput) -> i64
     {
         assert!(!input.players.is_empty());
         assert!(!input.closed_valves.is_empty());
@@ -234,7 +233,7 @@ impl Map
         //    that can be released by opening them.
         // 3) Find the maximum - or zero if there are no more moves possible
 
-        self.move_players(input)
+        map.move_players(input)
             .into_iter()
             .map(|(new_input, direct_pressure_release)|
             {
@@ -245,27 +244,12 @@ impl Map
                 else
                 {
                     direct_pressure_release
-                        + recurse.get(&new_input)
+                        + most_pressure_released_from_loc_to_closed_valves_in_time(map, &new_input)
                 }
             })
             .max()
             .unwrap_or(0)
     }
-
-    fn most_pressure_released(&self, num_players: usize, time_to_work: i64) -> i64
-    {
-        Memorized::new(
-            &|input, recurse|
-            {
-                self.most_pressure_released_from_loc_to_closed_valves_in_time(input, recurse)
-            })
-            //.debug(true)
-            .get(&MemorizedInput
-            {
-                players: vec![("AA".to_string(), time_to_work); num_players],
-                closed_valves: self.valves.clone(),
-            })
-    }
 }
 
 fn most_pressure_released(input: &str, num_players: usize, time_to_work: i64) -> i64
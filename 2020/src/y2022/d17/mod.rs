@@ -1,6 +1,6 @@
 use crate::support::*;
 use itertools::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashSet, VecDeque};
 
 const EXAMPLE: &str = include_str!("example.txt");
 
@@ -34,16 +34,12 @@ impl Rock
 }
 
 #[derive(PartialEq, Eq, Hash)]
-struct SearchKey
+struct CycleKey
 {
-    rock_0_jet_index: usize,
-    rock_offset: Vec<Point>,
-}
-
-struct SearchValue
-{
-    num_rocks_placed: u64,
-    post_rock_n_height: i64,
+    rock_index: usize,
+    jet_index: usize,
+    skyline: [i64; 7],
+    rock_offsets: Vec<Point>,
 }
 
 struct Board
@@ -52,16 +48,10 @@ struct Board
     rocks: Vec<Rock>,
     jet_patten: Vec<char>,
     rock_index: usize,
-    num_rocks_placed: u64,
     jet_index: usize,
-    pre_rock_0_height: i64,
-    rock_0_jet_index: usize,
-    rock_0_pos: Point,
-    rock_offsets: Vec<Point>,
-    cycle_search: HashMap<SearchKey, SearchValue>,
-    cycle_time_in_blocks: Option<u64>,
-    cycle_height: Option<i64>,
-    faked_height: i64,
+    height: i64,
+    column_heights: [i64; 7],
+    recent_rocks: VecDeque<Point>,
 }
 
 impl Board
@@ -74,26 +64,16 @@ impl Board
             rocks: Rock::all(),
             jet_patten: input_to_lines(input)[0].chars().collect_vec(),
             rock_index: 0,
-            num_rocks_placed: 0,
             jet_index: 0,
-            pre_rock_0_height: 0,
-            rock_0_jet_index: 0,
-            rock_0_pos: Point::new(0, 0),
-            rock_offsets: Vec::new(),
-            cycle_search: HashMap::new(),
-            cycle_time_in_blocks: None,
-            cycle_height: None,
-            faked_height: 0,
+            height: 0,
+            column_heights: [0; 7],
+            recent_rocks: VecDeque::new(),
         }
     }
 
     fn height(&self) -> i64
     {
-        self.faked_height
-            +  self.filled.iter()
-                    .map(|p| p.y)
-                    .max()
-                    .unwrap_or(0)
+        self.height
     }
 
     #[allow(unused)]
@@ -105,6 +85,24 @@ impl Board
             .collect())
     }
 
+    fn cycle_key(&self) -> CycleKey
+    {
+        // The shape of the top of the tower, and where
+        // the last few rocks landed relative to each other -
+        // along with the next rock and jet - is assumed to
+        // determine the rest of the tower
+
+        let first = self.recent_rocks.front().copied().unwrap_or(Point::new(0, 0));
+
+        CycleKey
+        {
+            rock_index: self.rock_index,
+            jet_index: self.jet_index,
+            skyline: self.column_heights.map(|h| self.height - h),
+            rock_offsets: self.recent_rocks.iter().map(|p| *p - first).collect(),
+        }
+    }
+
     fn can_place_rock(&self, rock_index: usize, pos: Point) -> bool
     {
         self.rocks[rock_index].points.iter()
@@ -120,14 +118,11 @@ impl Board
 
     fn place_single_rock(&mut self)
     {
-        let start_height = self.height() - self.faked_height;
-        let mut cur_pos = Point::new(2, 4 + start_height);
+        let mut cur_pos = Point::new(2, 4 + self.height);
 
         let rock_index = self.rock_index;
         self.rock_index = (self.rock_index + 1) % self.rocks.len();
 
-        let start_jet_index = self.jet_index;
-
         loop
         {
             // Push by jet
@@ -160,63 +155,16 @@ impl Board
 
                 for p in &self.rocks[rock_index].points
                 {
-                    self.filled.insert(cur_pos + *p);
+                    let p = cur_pos + *p;
+                    self.filled.insert(p);
+                    self.height = self.height.max(p.y);
+                    self.column_heights[p.x as usize] = self.column_heights[p.x as usize].max(p.y);
                 }
 
-                self.num_rocks_placed += 1;
-
-                if rock_index == 0
+                self.recent_rocks.push_back(cur_pos);
+                if self.recent_rocks.len() > self.rocks.len()
                 {
-                    self.rock_0_jet_index = start_jet_index;
-                    self.rock_0_pos = cur_pos;
-                    self.rock_offsets.clear();
-                }
-                else if self.cycle_time_in_blocks.is_none()
-                {
-                    // Perform the search for a cycle
-
-                    self.rock_offsets.push(cur_pos - self.rock_0_pos);
-
-                    if rock_index + 1 == self.rocks.len()
-                    {
-                        self.pre_rock_0_height = self.height();
-
-                        match self.cycle_search.insert(
-                            SearchKey
-                            {
-                                rock_0_jet_index: self.rock_0_jet_index,
-                                rock_offset: self.rock_offsets.clone(),
-                            },
-                            SearchValue
-                            {
-                                num_rocks_placed: self.num_rocks_placed,
-                                post_rock_n_height: self.pre_rock_0_height,
-                            })
-                        {
-                            None =>
-                            {
-                                // OK - no cycle found - keep searching
-                            },
-                            Some(search_value) =>
-                            {
-                                // We've found a cycle!
-
-                                self.cycle_time_in_blocks = Some(
-                                    self.num_rocks_placed - search_value.num_rocks_placed);
-
-                                self.cycle_height = Some(
-                                    self.pre_rock_0_height - search_value.post_rock_n_height);
-
-                                //println!("Cycle found: {} .. {}, {} blocks, {} height",
-                                //    search_value.num_rocks_placed,
-                                //    self.num_rocks_placed,
-                                //    self.cycle_time_in_blocks.unwrap(),
-                                //    self.cycle_height.unwrap());
-                            },
-                        }
-                    }
-
-                    self.pre_rock_0_height = self.height();
+                    self.recent_rocks.pop_front();
                 }
 
                 //println!("Placed block {} at {:?}", rock_index, cur_pos);
@@ -225,40 +173,19 @@ impl Board
             }
         }
     }
-
-    fn place_n_rocks(&mut self, n: u64)
-    {
-        let mut remaining = n;
-
-        while remaining >= 5 && self.cycle_time_in_blocks.is_none()
-        {
-            for _ in 0..5 { self.place_single_rock(); }
-            remaining -= 5;
-        }
-
-        if self.cycle_time_in_blocks.is_some()
-        {
-            let num_cycles = remaining / self.cycle_time_in_blocks.unwrap();
-            remaining = remaining % self.cycle_time_in_blocks.unwrap();
-
-            self.faked_height += (num_cycles as i64) * self.cycle_height.unwrap();
-
-            //println!("Faked {} cycles to add {} height => {} remaining",
-            //    num_cycles, self.faked_height, remaining);
-        }
-
-        for _ in 0..remaining
-        {
-            self.place_single_rock();
-        }
-    }
 }
 
 fn height_after_rocks(input: &str, num_rocks: u64) -> i64
 {
-    let mut board = Board::new(input);
-    board.place_n_rocks(num_rocks);
-    board.height()
+    // The tower soon starts repeating, so
+    // extrapolate its height from that
+
+    metric_after_steps(
+        Board::new(input),
+        |b| b.place_single_rock(),
+        |b| b.cycle_key(),
+        |b| b.height(),
+        num_rocks)
 }
 
 fn part_1(input: &str) -> i64
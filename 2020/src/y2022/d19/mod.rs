@@ -184,7 +184,11 @@ impl Blueprint
             robots: [1, 0, 0, 0],
         };
 
-        let search_result = search(
+        // Explore the most promising states first, so
+        // that the search can stop as soon as no state
+        // could beat the best solution
+
+        let search_result = Search::new(
             start,
             |s|
             {
@@ -199,13 +203,17 @@ impl Blueprint
                 // Want most geodes - so
                 // search in reverse order
                 b.resources[3].cmp(&a.resources[3])
-            },
-            |state, best_solution|
+            })
+            .prune(|state, best_solution|
             {
                 let estimate = state.greedy_estimate_of_max_geodes(self, minutes);
                 estimate > best_solution.resources[3]
-            },
-            SearchDebugLevel::None).unwrap();
+            })
+            .best_first(|state| state.greedy_estimate_of_max_geodes(self, minutes))
+            .dedup(|state| (state.elapsed, state.resources, state.robots))
+            .run()
+            .best
+            .unwrap();
 
         search_result.resources[3]
     }
@@ -143,207 +143,44 @@ trait Layout
     }
 }
 
-struct ExampleLayout
+struct NetLayout
 {
-    size: i64,
+    net: CubeNet,
     part2: bool
 }
 
-impl ExampleLayout
+impl NetLayout
 {
-    fn new(size: i64, part2: bool) -> Self
+    fn new(grid: &CharGrid, part2: bool) -> Self
     {
-        ExampleLayout { size, part2 }
+        NetLayout { net: CubeNet::new_from_grid(grid, ' '), part2 }
     }
 }
 
-impl Layout for ExampleLayout
+impl Layout for NetLayout
 {
     fn face_size(&self) -> i64
     {
-        self.size
+        self.net.face_size()
     }
 
     fn fp_to_gp(&self, fp: &FacePoint) -> Point
     {
-        match fp.face
-        {
-            0 => fp.point + self.size * Point::new(2, 0),
-            1 => fp.point + self.size * Point::new(0, 1),
-            2 => fp.point + self.size * Point::new(1, 1),
-            3 => fp.point + self.size * Point::new(2, 1),
-            4 => fp.point + self.size * Point::new(2, 2),
-            5 => fp.point + self.size * Point::new(3, 2),
-            _ => unreachable!(),
-        }
+        fp.point + self.net.face_origin(fp.face as usize)
     }
 
     fn fold_info(&self, face: i64, dir: Dir) -> (i64, Dir, bool)
     {
-        if !self.part2 // - wrap net
+        let edge = if self.part2
         {
-            match (face, dir)
-            {
-                (0, Dir::Right) => (0, Dir::Right, false),
-                (0, Dir::Down)  => (3, Dir::Down, false),
-                (0, Dir::Left)  => (0, Dir::Left, false),
-                (0, Dir::Up)    => (4, Dir::Up, false),
-                (1, Dir::Right) => (2, Dir::Right, false),
-                (1, Dir::Down)  => (1, Dir::Down, false),
-                (1, Dir::Left)  => (3, Dir::Left, false),
-                (1, Dir::Up)    => (1, Dir::Up, false),
-                (2, Dir::Right) => (3, Dir::Right, false),
-                (2, Dir::Down)  => (2, Dir::Down, false),
-                (2, Dir::Left)  => (1, Dir::Left, false),
-                (2, Dir::Up)    => (2, Dir::Up, false),
-                (3, Dir::Right) => (1, Dir::Right, false),
-                (3, Dir::Down)  => (4, Dir::Down, false),
-                (3, Dir::Left)  => (2, Dir::Left, false),
-                (3, Dir::Up)    => (0, Dir::Up, false),
-                (4, Dir::Right) => (5, Dir::Right, false),
-                (4, Dir::Down)  => (0, Dir::Down, false),
-                (4, Dir::Left)  => (5, Dir::Left, false),
-                (4, Dir::Up)    => (3, Dir::Up, false),
-                (5, Dir::Right) => (4, Dir::Right, false),
-                (5, Dir::Down)  => (5, Dir::Down, false),
-                (5, Dir::Left)  => (4, Dir::Left, false),
-                (5, Dir::Up)    => (5, Dir::Up, false),
-                _ => unreachable!(),
-            }
+            self.net.folded_edge(face as usize, dir.to_int() as usize)
         }
-        else // part2 - fold into a cube
-        {
-            match (face, dir)
-            {
-                (0, Dir::Right) => (5, Dir::Left, true),
-                (0, Dir::Down)  => (3, Dir::Down, false),
-                (0, Dir::Left)  => (2, Dir::Down, false),
-                (0, Dir::Up)    => (1, Dir::Down, true),
-                (1, Dir::Right) => (2, Dir::Right, false),
-                (1, Dir::Down)  => (4, Dir::Up, true),
-                (1, Dir::Left)  => (5, Dir::Up, true),
-                (1, Dir::Up)    => (0, Dir::Down, true),
-                (2, Dir::Right) => (3, Dir::Right, false),
-                (2, Dir::Down)  => (4, Dir::Right, true),
-                (2, Dir::Left)  => (1, Dir::Left, false),
-                (2, Dir::Up)    => (0, Dir::Right, false),
-                (3, Dir::Right) => (5, Dir::Down, true),
-                (3, Dir::Down)  => (4, Dir::Down, false),
-                (3, Dir::Left)  => (2, Dir::Left, false),
-                (3, Dir::Up)    => (0, Dir::Up, false),
-                (4, Dir::Right) => (5, Dir::Right, false),
-                (4, Dir::Down)  => (1, Dir::Up, true),
-                (4, Dir::Left)  => (2, Dir::Up, true),
-                (4, Dir::Up)    => (3, Dir::Up, false),
-                (5, Dir::Right) => (0, Dir::Left, true),
-                (5, Dir::Down)  => (1, Dir::Right, true),
-                (5, Dir::Left)  => (4, Dir::Left, false),
-                (5, Dir::Up)    => (3, Dir::Left, true),
-                _ => unreachable!(),
-            }
-        }
-    }
-}
-
-struct MyInputLayout
-{
-    size: i64,
-    part2: bool
-}
-
-impl MyInputLayout
-{
-    fn new(size: i64, part2: bool) -> Self
-    {
-        MyInputLayout { size, part2 }
-    }
-}
-
-impl Layout for MyInputLayout
-{
-    fn face_size(&self) -> i64
-    {
-        self.size
-    }
-
-    fn fp_to_gp(&self, fp: &FacePoint) -> Point
-    {
-        match fp.face
+        else
         {
-            0 => fp.point + self.size * Point::new(1, 0),
-            1 => fp.point + self.size * Point::new(2, 0),
-            2 => fp.point + self.size * Point::new(1, 1),
-            3 => fp.point + self.size * Point::new(0, 2),
-            4 => fp.point + self.size * Point::new(1, 2),
-            5 => fp.point + self.size * Point::new(0, 3),
-            _ => unreachable!(),
-        }
-    }
+            self.net.wrapped_edge(face as usize, dir.to_int() as usize)
+        };
 
-    fn fold_info(&self, face: i64, dir: Dir) -> (i64, Dir, bool)
-    {
-        if !self.part2 // - wrap net
-        {
-            match (face, dir)
-            {
-                (0, Dir::Right) => (1, Dir::Right, false),
-                (0, Dir::Down)  => (2, Dir::Down, false),
-                (0, Dir::Left)  => (1, Dir::Left, false),
-                (0, Dir::Up)    => (4, Dir::Up, false),
-                (1, Dir::Right) => (0, Dir::Right, false),
-                (1, Dir::Down)  => (1, Dir::Down, false),
-                (1, Dir::Left)  => (0, Dir::Left, false),
-                (1, Dir::Up)    => (1, Dir::Up, false),
-                (2, Dir::Right) => (2, Dir::Right, false),
-                (2, Dir::Down)  => (4, Dir::Down, false),
-                (2, Dir::Left)  => (2, Dir::Left, false),
-                (2, Dir::Up)    => (0, Dir::Up, false),
-                (3, Dir::Right) => (4, Dir::Right, false),
-                (3, Dir::Down)  => (5, Dir::Down, false),
-                (3, Dir::Left)  => (4, Dir::Left, false),
-                (3, Dir::Up)    => (5, Dir::Up, false),
-                (4, Dir::Right) => (3, Dir::Right, false),
-                (4, Dir::Down)  => (0, Dir::Down, false),
-                (4, Dir::Left)  => (3, Dir::Left, false),
-                (4, Dir::Up)    => (2, Dir::Up, false),
-                (5, Dir::Right) => (5, Dir::Right, false),
-                (5, Dir::Down)  => (3, Dir::Down, false),
-                (5, Dir::Left)  => (5, Dir::Left, false),
-                (5, Dir::Up)    => (3, Dir::Up, false),
-                _ => unreachable!(),
-            }
-        }
-        else // part2 - fold into a cube
-        {
-            match (face, dir)
-            {
-                (0, Dir::Right) => (1, Dir::Right, false),
-                (0, Dir::Down)  => (2, Dir::Down, false),
-                (0, Dir::Left)  => (3, Dir::Right, true),
-                (0, Dir::Up)    => (5, Dir::Right, false),
-                (1, Dir::Right) => (4, Dir::Left, true),
-                (1, Dir::Down)  => (2, Dir::Left, false),
-                (1, Dir::Left)  => (0, Dir::Left, false),
-                (1, Dir::Up)    => (5, Dir::Up, false),
-                (2, Dir::Right) => (1, Dir::Up, false),
-                (2, Dir::Down)  => (4, Dir::Down, false),
-                (2, Dir::Left)  => (3, Dir::Down, false),
-                (2, Dir::Up)    => (0, Dir::Up, false),
-                (3, Dir::Right) => (4, Dir::Right, false),
-                (3, Dir::Down)  => (5, Dir::Down, false),
-                (3, Dir::Left)  => (0, Dir::Right, true),
-                (3, Dir::Up)    => (2, Dir::Right, false),
-                (4, Dir::Right) => (1, Dir::Left, true),
-                (4, Dir::Down)  => (5, Dir::Left, false),
-                (4, Dir::Left)  => (3, Dir::Left, false),
-                (4, Dir::Up)    => (2, Dir::Up, false),
-                (5, Dir::Right) => (4, Dir::Up, false),
-                (5, Dir::Down)  => (1, Dir::Down, false),
-                (5, Dir::Left)  => (0, Dir::Down, false),
-                (5, Dir::Up)    => (3, Dir::Up, false),
-                _ => unreachable!(),
-            }
-        }
+        (edge.face as i64, Dir::from_int(edge.dir as i64), edge.reversed)
     }
 }
 
@@ -463,17 +300,9 @@ fn get_password(input: &str, part2: bool) -> i64
 {
     let (grid, moves) = parse(input);
 
-    // Work out which layout to use
+    // Work out the layout from the shape of the net
 
-    let layout: Box<dyn Layout>;
-    if grid.get_width() == 16
-    {
-        layout = Box::new(ExampleLayout::new(4, part2));
-    }
-    else
-    {
-        layout = Box::new(MyInputLayout::new(50, part2));
-    }
+    let layout: Box<dyn Layout> = Box::new(NetLayout::new(&grid, part2));
 
     // Check the cube folding is correct
 
@@ -1,5 +1,5 @@
 use crate::support::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 const EXAMPLE: &str = include_str!("example.txt");
 
@@ -23,15 +23,15 @@ fn one_round(input: Vec<Point>, round_num: usize) -> (Vec<Point>, bool)
         [ Point{x:1,y:-1}, Point{x:1,y:0}, Point{x:1,y:1}],
     ];
 
-    let input: HashSet<Point> = input.into_iter().collect();
+    let input = SparseGrid::new_from_points(input, '#');
     let mut dests: HashMap<Point, Vec<Point>> = HashMap::new();
 
-    for elf_pos in input.iter()
+    for elf_pos in input.points()
     {
-        let mut dest = elf_pos.clone();
+        let mut dest = elf_pos;
 
-        let any_neighbour_occupied = elf_pos.neighbours_8()
-            .any(|n| input.contains(&n));
+        let any_neighbour_occupied = input.neighbours_8(&elf_pos)
+            .next().is_some();
 
         if any_neighbour_occupied
         {
@@ -40,18 +40,18 @@ fn one_round(input: Vec<Point>, round_num: usize) -> (Vec<Point>, bool)
                 let dirs = &DIRS[(round_num + offset) % 4];
 
                 let all_free_in_dir = dirs.iter()
-                    .map(|dir| *elf_pos + *dir)
+                    .map(|dir| elf_pos + *dir)
                     .all(|n| !input.contains(&n));
 
                 if all_free_in_dir
                 {
-                    dest = *elf_pos + dirs[1];
+                    dest = elf_pos + dirs[1];
                     break;
                 }
             }
         }
 
-        dests.entry(dest).or_default().push(elf_pos.clone());
+        dests.entry(dest).or_default().push(elf_pos);
     }
 
     let mut any_moved = false;
@@ -83,12 +83,7 @@ fn part_1(input: &str) -> usize
         (elfs, _) = one_round(elfs, round);
     }
 
-    let grid = CharGrid::new_from_points(elfs);
-
-    grid.all_chars()
-        .into_iter()
-        .filter(|c| *c == '.')
-        .count()
+    SparseGrid::new_from_points(elfs, '#').num_empty_in_bounds()
 }
 
 fn part_2(input: &str) -> usize
@@ -3,90 +3,36 @@ use itertools::*;
 
 const EXAMPLE: &str = include_str!("example.txt");
 
-struct SourceDestEntry
-{
-    source_range: RangeInc<u64>,
-    dest_start: u64,
-}
-
-struct SourceDestMap
-{
-    entries: Vec<SourceDestEntry>
-}
-
-impl SourceDestMap
-{
-    fn map_ranges(&self, ranges: &RangeSet<u64>) -> RangeSet<u64>
-    {
-        // First:
-        // 1) Start with an empty result set
-        // 2) Mark all inputs as unmapped
-
-        let mut result = RangeSet::new();
-        let mut unmapped = ranges.clone();
-
-        // Now - for each entry in our mapping:
-        // 1) Find the intersection with the unmapped entries,
-        // 2) Remove these from the umapped entries
-        // 3) Insert the mapped values into the result
-
-        for entry in self.entries.iter()
-        {
-            let mut intersection = unmapped.clone();
-            intersection.insersect_with_range(entry.source_range);
-
-            for range in intersection.ranges()
-            {
-                let start_offset = range.start - entry.source_range.start;
-                let end_offset = range.end - entry.source_range.start;
-
-                unmapped.remove_range(range);
-                result.insert_range(RangeInc::new_range(entry.dest_start + start_offset, entry.dest_start + end_offset));
-            }
-        }
-
-        // Finally - any un-mapped entries remaining
-        // pass straigh through
-
-        for unmapped_range in unmapped.ranges()
-        {
-            result.insert_range(unmapped_range);
-        }
-
-        result
-    }
-}
-
 struct Almanac
 {
-    seeds: RangeSet<u64>,
-    maps: Vec<SourceDestMap>,
+    seeds: RangeSet<i64>,
+    maps: Vec<RangeMap<i64, i64>>,
 }
 
 impl Almanac
 {
     fn parse(input: &str, part_2: bool) -> Self
     {
-        let parse_line = |l: &str|
-        {
-            let parts = l.split(" ").map(|p| p.parse::<u64>().unwrap()).collect_vec();
-            assert!(parts.len() == 3);
-            let source_range = RangeInc::new_range(parts[1], parts[1] + parts[2] - 1);
-            let dest_start = parts[0];
-            SourceDestEntry { source_range, dest_start }
-        };
-
         let parse_map = |g: &Vec<String>|
         {
-            let entries = g[1..].iter()
-                .map(|l| parse_line(l))
-                .collect_vec();
-            SourceDestMap { entries }
+            // Each line moves a source range by an offset -
+            // and anything not in a range stays the same
+
+            let mut map = RangeMap::new();
+
+            for l in g[1..].iter()
+            {
+                let parts = l.split(" ").map(|p| p.parse::<i64>().unwrap()).collect_vec();
+                assert!(parts.len() == 3);
+                map.insert(RangeInc::new_range(parts[1], parts[1] + parts[2] - 1), parts[0] - parts[1]);
+            }
+
+            map
         };
 
         let groups = input_to_groups(input);
 
-        let seed_list = groups[0][0].split(" ").skip(1).map(|s| s.parse::<u64>().unwrap()).collect_vec();
+        let seed_list = groups[0][0].split(" ").skip(1).map(|s| s.parse::<i64>().unwrap()).collect_vec();
         let maps = groups[1..].iter().map(|g| parse_map(g)).collect_vec();
 
         let seeds = if part_2
@@ -111,16 +57,15 @@ impl Almanac
         Almanac { seeds, maps }
     }
 
-    fn map_seeds(&self, seeds: RangeSet<u64>) -> RangeSet<u64>
+    fn map_seeds(&self, seeds: RangeSet<i64>) -> RangeSet<i64>
     {
-        let mut cur = seeds;
-        //println!("{:?}", cur);
-        for map in self.maps.iter()
-        {
-            cur = map.map_ranges(&cur);
-            //println!("    => {:?}", cur);
-        }
-        cur
+        // Collapse seed => soil => ... => location
+        // into a single map
+
+        let seed_to_location = self.maps.iter()
+            .fold(RangeMap::new(), |all, map| all.compose(map));
+
+        seed_to_location.map_set(&seeds)
     }
 }
 
@@ -128,14 +73,14 @@ fn part_1(input: &str) -> u64
 {
     let almanac = Almanac::parse(input, false);
 
-    almanac.map_seeds(almanac.seeds.clone()).values().next().unwrap()
+    almanac.map_seeds(almanac.seeds.clone()).values().next().unwrap() as u64
 }
 
 fn part_2(input: &str) -> u64
 {
     let almanac = Almanac::parse(input, true);
 
-    almanac.map_seeds(almanac.seeds.clone()).values().next().unwrap()
+    almanac.map_seeds(almanac.seeds.clone()).values().next().unwrap() as u64
 }
 
 pub fn puzzles() -> PuzzleDay
@@ -67,32 +67,6 @@ fn find_path(map: &CharGrid) -> Vec<Point>
     }
 }
 
-fn count_crossings<FExtract: Fn(&Point) -> i64, FBuild: Fn(i64) -> Point>(map: &CharGrid, path_points: &HashSet<Point>, point: &Point, extract: FExtract, build: FBuild) -> i64
-{
-    let mut inc_count = 0;
-    let mut dec_count = 0;
-
-    for i in 0..extract(&point)
-    {
-        let p = build(i);
-
-        if path_points.contains(&p)
-        {
-            let ch = map.get_char(&p);
-            let junction_dirs = junctions_to_char(ch);
-
-            for dir in junction_dirs
-            {
-                let dir = extract(&Point::new(dir.y, dir.x));
-                if dir > 0 { inc_count += 1; }
-                if dir < 0 { dec_count += 1; }
-            }
-        }
-    }
-
-    inc_count.min(dec_count)
-}
-
 fn part_1(input: &str) -> usize
 {
     let map = CharGrid::new_from_input(input, '.');
@@ -101,55 +75,13 @@ fn part_1(input: &str) -> usize
 
 fn part_2(input: &str) -> usize
 {
-    let map = CharGrid::new_from_input(input, '.');
-    let path = find_path(&map);
-    let starting_point = path[0].clone();
-    let path_points = path.into_iter().collect::<HashSet<_>>();
-
-    // Flood fill a map with all points "outside"
+    // The pipe loop is the boundary of a polygon -
+    // we want the points strictly inside it
 
-    let mut inside_map = CharGrid::new_from_fill(map.get_width() as usize, map.get_height() as usize, 'O');
-
-    // Now - process each point
-
-    for point in inside_map.all_points()
-    {
-        if path_points.contains(&point)
-        {
-            inside_map.put_char(&point, map.get_char(&point));
-        }
-        else
-        {
-            // To find points inside:
-            // Send a 'ray' to infinity - points inside
-            // will 'cross' the path an odd number of times.
-            // Because the starting point doesn't have
-            // a known piece of pipe - we need to test
-            // in both directions to ensure we don't pass
-            // through the starting point
-
-            let crossings = if point.y != starting_point.y
-            {
-                count_crossings(&map, &path_points, &point, |p| p.x, |x| Point::new(x, point.y))
-            }
-            else
-            {
-                count_crossings(&map, &path_points, &point, |p| p.y, |y| Point::new(point.x, y))
-            };
-
-            if (crossings % 2) == 1
-            {
-                inside_map.put_char(&point, 'I')
-            }
-        }
-    }
+    let map = CharGrid::new_from_input(input, '.');
 
-    //println!("{}", inside_map.to_string());
-    
-    inside_map.all_chars()
-        .into_iter()
-        .filter(|ch| *ch == 'I')
-        .count()
+    Polygon::new_from_points(find_path(&map))
+        .num_interior_points() as usize
 }
 
 pub fn puzzles() -> PuzzleDay
@@ -78,46 +78,18 @@ impl std::fmt::Debug for Row
     }
 }
 
-#[derive(Hash, PartialEq, Eq, Clone)]
-struct MemorizeState<'a>
+memorize!
 {
-    springs: &'a [Spring],
-    damaged_counts: &'a [usize],
-    remaining_damage: Option<usize>,
-}
+    // Within a single row, the remaining springs and
+    // damaged counts are identified by their lengths
 
-impl<'a> std::fmt::Debug for MemorizeState<'a>
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    #[key(springs.len(), damaged_counts.len(), remaining_damage)]
+    fn count_arrangements(springs: &[Spring], damaged_counts: &[usize], remaining_damage: Option<usize>) -> usize
     {
-        f.write_str(&self.springs.iter().map(|s| s.to_char()).collect::<String>())?;
-        f.write_str(" ")?;
-        f.write_str(&self.damaged_counts.iter().map(|d| d.to_string()).join(","))?;
-
-        if self.remaining_damage.is_some()
+        if (springs.len() == 0) && (damaged_counts.len() == 0)
         {
-            write!(f, " {:?}", self.remaining_damage)?;
-        }
-
-        Ok(())
-    }
-}
-
-fn count_arrangements<'a>(springs: &'a[Spring], damaged_counts: &'a[usize]) -> usize
-{
-    let initial_state = MemorizeState
-    {
-        springs,
-        damaged_counts,
-        remaining_damage: None,
-    };
-
-    Memorized::new(&move |input: &MemorizeState<'a>, memorized| -> usize
-    {
-        if (input.springs.len() == 0) && (input.damaged_counts.len() == 0)
-        {
-            if input.remaining_damage.is_some()
-                && (input.remaining_damage.unwrap() > 0)
+            if remaining_damage.is_some()
+                && (remaining_damage.unwrap() > 0)
             {
                 // Still remaining damage - not valid
                 return 0;
@@ -128,7 +100,7 @@ fn count_arrangements<'a>(springs: &'a[Spring], damaged_counts: &'a[usize]) -> u
                 return 1;
             }
         }
-        else if input.springs.len() == 0
+        else if springs.len() == 0
         {
             // There is more damage but no springs remaining -
             // not a valid arangement
@@ -136,9 +108,9 @@ fn count_arrangements<'a>(springs: &'a[Spring], damaged_counts: &'a[usize]) -> u
         }
         else // there is a next spring to process
         {
-            let next_spring = input.springs[0].clone();
+            let next_spring = springs[0].clone();
 
-            match input.remaining_damage
+            match remaining_damage
             {
                 Some(remaining_damage) =>
                 {
@@ -147,12 +119,7 @@ fn count_arrangements<'a>(springs: &'a[Spring], damaged_counts: &'a[usize]) -> u
                         if next_spring.could_be_operational()
                         {
                             // Next spring completes damaged run
-                            return memorized.get(&MemorizeState
-                            {
-                                springs: &input.springs[1..],
-                                damaged_counts: &input.damaged_counts,
-                                remaining_damage: None,
-                            });
+                            return count_arrangements(&springs[1..], damaged_counts, None);
                         }
                         else
                         {
@@ -168,27 +135,19 @@ fn count_arrangements<'a>(springs: &'a[Spring], damaged_counts: &'a[usize]) -> u
                     else
                     {
                         // Consume spring as damaged
-                        return memorized.get(&MemorizeState {
-                            springs: &input.springs[1..],
-                            damaged_counts: &input.damaged_counts,
-                            remaining_damage: Some(remaining_damage - 1),
-                        });
+                        return count_arrangements(&springs[1..], damaged_counts, Some(remaining_damage - 1));
                     }
                 },
                 None =>
                 {
                     // We are skipping operational springs
                     // to get to the next damaged run
-                    if input.damaged_counts.len() == 0
+                    if damaged_counts.len() == 0
                     {
                         // No more damaged counts - we can only accept operational springs
                         if next_spring.could_be_operational()
                         {
-                            return memorized.get(&MemorizeState {
-                                springs: &input.springs[1..],
-                                damaged_counts: &input.damaged_counts,
-                                remaining_damage: None,
-                            })
+                            return count_arrangements(&springs[1..], damaged_counts, None)
                         }
                         else // next spring is damaged - not valid
                         {
@@ -200,7 +159,7 @@ fn count_arrangements<'a>(springs: &'a[Spring], damaged_counts: &'a[usize]) -> u
                         // OK - we've got both a next spring and
                         // a next damaged count - there are two possibilities...
 
-                        let next_damaged_count = input.damaged_counts[0];
+                        let next_damaged_count = damaged_counts[0];
                         assert!(next_damaged_count > 0);
 
                         let mut result = 0;
@@ -208,28 +167,20 @@ fn count_arrangements<'a>(springs: &'a[Spring], damaged_counts: &'a[usize]) -> u
                         if next_spring.could_be_operational()
                         {
                             // Treat the spring as operational
-                            result += memorized.get(&MemorizeState {
-                                springs: &input.springs[1..],
-                                damaged_counts: &input.damaged_counts,
-                                remaining_damage: None,
-                            });
+                            result += count_arrangements(&springs[1..], damaged_counts, None);
                         }
 
                         if next_spring.could_be_damaged()
                         {
                             // Treat the spring as a new run of damaged springs
-                            result += memorized.get(&MemorizeState {
-                                springs: &input.springs[1..],
-                                damaged_counts: &input.damaged_counts[1..],
-                                remaining_damage: Some(next_damaged_count - 1),
-                            });
+                            result += count_arrangements(&springs[1..], &damaged_counts[1..], Some(next_damaged_count - 1));
                         }
                         return result;
                     }
                 },
             }
         }
-    }).get(&initial_state)
+    }
 }
 
 impl Row
@@ -254,7 +205,7 @@ impl Row
 
     fn count_arrangements(& self) -> usize
     {
-        count_arrangements(&self.springs, &self.damaged_counts)
+        count_arrangements(&self.springs, &self.damaged_counts, None)
     }
 
 }
@@ -1,5 +1,4 @@
 use crate::support::*;
-use std::collections::HashMap;
 
 const EXAMPLE: &str = include_str!("example.txt");
 
@@ -67,46 +66,18 @@ fn part_1(input: &str) -> i64
 
 fn part_2(input: &str) -> i64
 {
-    const CYCLES: i64 = 1000000000;
+    // Spin cycles soon start repeating, so we
+    // can skip ahead to the final state
 
-    let mut grid = CharGrid::new_from_input(input, '.');
-
-    // Keep performing spin cycles until we get back to the same point
-    let mut seen_states = HashMap::new();
-    let mut states_in_order = Vec::new();
+    const CYCLES: u64 = 1000000000;
 
-    seen_states.insert(grid.to_string(), 0);
-    states_in_order.push(grid.clone());
-
-    let mut num_spin_cycles = 0;
-    let cycle_start;
-    loop
-    {
-        spin_cycle(&mut grid);
-        num_spin_cycles += 1;
+    let grid = state_after_steps(
+        CharGrid::new_from_input(input, '.'),
+        spin_cycle,
+        |g| g.clone(),
+        CYCLES);
 
-        match seen_states.insert(grid.to_string(), num_spin_cycles)
-        {
-            None =>
-            {
-                // OK - another new state
-            },
-            Some(prev_val) =>
-            {
-                cycle_start = prev_val;
-                break;
-            }
-        }
-        states_in_order.push(grid.clone());
-    }
-
-    // The state after 1,000,000,000 cycles will be
-    // the same after being reduced mod the cycle length -
-    // with an offset for the cycle start...
-
-    let modulus = cycle_start + ((CYCLES - cycle_start) % (num_spin_cycles - cycle_start));
-
-    calc_load_on_north_beam(&states_in_order[modulus as usize])
+    calc_load_on_north_beam(&grid)
 }
 
 pub fn puzzles() -> PuzzleDay
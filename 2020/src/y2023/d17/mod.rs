@@ -94,13 +94,12 @@ fn find_best_path(input: &str, size: CruicibleSize) -> usize
         steps_in_dir: 0,
     };
 
-    let result = pathfinding::directed::astar::astar(
-        &initial,
+    let result = dijkstra_all_paths(
+        std::iter::once(initial),
         |s| s.neighbours(&grid, size),
-        |s| (s.pos - end_point).manhatten_size() as usize,
         |s| (s.pos == end_point) && s.valid_steps_to_complete(size));
 
-    result.unwrap().1
+    result.unwrap().cost()
 }
 
 fn part_1(input: &str) -> usize
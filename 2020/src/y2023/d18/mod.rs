@@ -89,103 +89,11 @@ fn calc_lagoon_volume(input: &str, format: ParseFormat) -> i64
         .map(|l| parse_plan_step(&l, format))
         .collect_vec();
 
-    // Create a set of lines
+    // The trench is the boundary of a polygon - the
+    // volume includes both the trench and the interior
 
-    let lines = steps_to_lines(steps);
-
-    let h_lines = lines.iter()
-        .filter(|l| l.start.y == l.end.y)
-        .cloned()
-        .collect_vec();
-
-    let v_lines = lines.iter()
-        .filter(|l| l.start.x == l.end.x)
-        .cloned()
-        .collect_vec();
-
-    // Find the y points of all horizontal lines
-
-    let h_ys = h_lines.iter()
-        .map(|l| l.start.y)
-        .unique()
-        .sorted()
-        .collect_vec();
-
-    // Now - step down each section between the
-    // horizontal lines, and add in the included rectangular area
-
-    let mut result = 0;
-
-    for (y1, y2) in h_ys.into_iter().tuple_windows()
-    {
-        assert!(y1 < y2);
-
-        // Find the vertical lines that are present at this y offset
-
-        let active_v_lines = v_lines.iter()
-            .filter(|&l|
-                {
-                    let ly_min = l.start.y.min(l.end.y);
-                    let ly_max = l.start.y.max(l.end.y);
-                    ly_min < y2 && ly_max > y1
-                })
-            .cloned()
-            .collect_vec();
-
-        // Find the sorted, unique x offsets of these lines.
-        // Vertical lines shouldn't connect - so unique x offsets
-        // should have the same size as the active V lines.
-        // It should also be even
-
-        let sorted_v_line_xs = active_v_lines.iter()
-            .map(|l| l.start.x)
-            .unique()
-            .sorted()
-            .collect_vec();
-
-        assert!(sorted_v_line_xs.len() == active_v_lines.len());
-        assert!(sorted_v_line_xs.len() % 2 == 0);
-
-        // Break these into pairs - and then range between each pair (inclusive)
-        // is definately included for this area from y1..y2
-
-        let mut xs_between_verticals = RangeSet::new();
-
-        for pair in sorted_v_line_xs.iter().chunks(2).into_iter().map(|c| c.cloned().collect_vec())
-        {
-            xs_between_verticals.insert_range(RangeInc::new_range(pair[0], pair[1]));
-        }
-
-        let mut xs_between_verticals_count = xs_between_verticals.count();
-
-        // If it's the first row, we need to include an extra
-        // count for the first horizontal row
-
-        if result == 0
-        {
-            result += xs_between_verticals_count;
-        }
-        
-        // Then add all the rows y1+1 .. y2-1
-
-        result += xs_between_verticals_count * (y2 - y1 - 1);
-
-        // Finally - we need to add on all of these points - PLUS
-        // any extra points on horizontal lines at y2
-
-        for hl_at_y2 in h_lines.iter().filter(|l| l.start.y == y2)
-        {
-            xs_between_verticals.insert_range(RangeInc::new_range(
-                hl_at_y2.start.x.min(hl_at_y2.end.x),
-                hl_at_y2.start.x.max(hl_at_y2.end.x)));
-        }
-
-        xs_between_verticals_count = xs_between_verticals.count();
-
-        result += xs_between_verticals_count;
-    }
-
-    result
+    Polygon::new_from_lines(&steps_to_lines(steps))
+        .num_enclosed_points()
 }
 
 fn part_1(input: &str) -> i64
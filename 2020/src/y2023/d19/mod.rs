@@ -213,99 +213,57 @@ impl Part
     }
 }
 
-fn split_rating_ranges(ratings: Vec<RangeSet<i64>>, comp: Option<Comparison>) -> (Option<Vec<RangeSet<i64>>>, Option<Vec<RangeSet<i64>>>)
+impl Comparison
 {
-    match comp
+    fn condition(&self) -> BoxCondition<i64>
     {
-        None =>
+        match self.op
         {
-            return (Some(ratings), None);
-        },
-        Some(comp) =>
-        {
-            let mut if_true = ratings.clone();
-            let mut if_false = ratings.clone();
-
-            match comp.op
-            {
-                CompareOp::GreaterThan =>
-                {
-                    // rating > comp.val : remove smaller from if_true, larger from if_false
-                    if_true[comp.rating_index].remove_range(RangeInc::new_range(0, comp.val));
-                    if_false[comp.rating_index].remove_range(RangeInc::new_range(comp.val + 1, 4001));
-                },
-                CompareOp::LessThan =>
-                {
-                    // rating < comp.val : remove larger from if_true, smaller from if_false
-                    if_true[comp.rating_index].remove_range(RangeInc::new_range(comp.val, 4001));
-                    if_false[comp.rating_index].remove_range(RangeInc::new_range(0, comp.val - 1));
-                },
-            };
-
-            let min_if_true = if_true.iter().map(|r| r.count()).min().unwrap();
-            let min_if_false = if_false.iter().map(|r| r.count()).min().unwrap();
-
-            let if_true = if min_if_true == 0 { None } else { Some(if_true) };
-            let if_false = if min_if_false == 0 { None } else { Some(if_false) };
-
-            (if_true, if_false)
-        },
+            CompareOp::LessThan => BoxCondition::LessThan(self.val),
+            CompareOp::GreaterThan => BoxCondition::GreaterThan(self.val),
+        }
     }
 }
 
-fn count_distinct_for_rule(ratings: Vec<RangeSet<i64>>, r_index: usize, rules: &Vec<Rule>, workflows: &HashMap<String, Workflow>) -> i64
+fn accepted_ratings(ratings: BoxSet<i64, 4>, workflow_name: &String, workflows: &HashMap<String, Workflow>) -> BoxSet<i64, 4>
 {
-    let (opt_if_true, opt_if_false) = split_rating_ranges(ratings, rules[r_index].comp);
+    // Each rule takes the ratings that match it,
+    // and passes the rest on to the next rule
 
-    let mut result = 0;
+    let mut remaining = ratings;
+    let mut result = BoxSet::new();
 
-    if let Some(if_true) = opt_if_true
+    for rule in workflows.get(workflow_name).unwrap().rules.iter()
     {
-        match &rules[r_index].action
+        if remaining.is_empty()
         {
-            Action::Accept =>
-            {
-                result += if_true.iter().map(|r| r.count()).product::<i64>();
-            },
-            Action::Reject =>
-            {
-                // Ignore
-            },
-            Action::Move(target) =>
-            {
-                result += count_distinct_for_workflow(if_true, target, workflows)
-            }
+            break;
         }
-    }
 
-    if let Some(if_false) = opt_if_false
-    {
-        result += count_distinct_for_rule(if_false, r_index + 1, rules, workflows);
-    }
+        let (matching, rest) = match rule.comp
+        {
+            Some(comp) => remaining.split(comp.rating_index, comp.condition()),
+            None => (remaining, BoxSet::new()),
+        };
 
-    result
-}
+        match &rule.action
+        {
+            Action::Accept => result = result.union(&matching),
+            Action::Reject => {},
+            Action::Move(target) => result = result.union(&accepted_ratings(matching, target, workflows)),
+        }
 
-fn count_distinct_for_workflow(ratings: Vec<RangeSet<i64>>, workflow_name: &String, workflows: &HashMap<String, Workflow>) -> i64
-{
-    if ratings.iter().map(|r| r.count()).min().unwrap() == 0
-    {
-        // At least one of the ratings has reached an empty range -
-        // the product will be zero. No more work required.
-        return 0;
+        remaining = rest;
     }
 
-    let workflow = workflows.get(workflow_name).unwrap();
-
-    count_distinct_for_rule(ratings, 0, &workflow.rules, workflows)
+    result
 }
 
 fn count_distinct_accepted_combinations(workflows: &HashMap<String, Workflow>) -> i64
 {
-    let range = RangeSet::new_from_range(RangeInc::new_range(1i64, 4000i64));
-    let initial_ratings = vec![range.clone(), range.clone(), range.clone(), range.clone()];
+    let all = HyperBox::new([RangeInc::new_range(1i64, 4000i64); 4]);
 
-    count_distinct_for_workflow(initial_ratings, &"in".to_string(), workflows)
+    accepted_ratings(BoxSet::new_from_box(all), &"in".to_string(), workflows).count().unwrap() as i64
 }
 
 fn parse_input(input: &str) -> (HashMap<String, Workflow>, Vec<Part>)
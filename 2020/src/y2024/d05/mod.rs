@@ -59,21 +59,27 @@ fn part_2(input: &str) -> usize
         .collect_vec();
 
     let fixed_updates = bad_updates.into_iter()
-        .map(|mut u|
+        .map(|u|
         {
-            u.sort_by(|a, b|
+            // Only the rules between pages in this
+            // update have to be followed
+
+            let mut graph = Graph::new_directed();
+
+            for page in u.iter()
             {
-                if rules.contains(&(*a, *b))
-                {
-                    return Ordering::Less;
-                }
-                if rules.contains(&(*b, *b))
+                graph.add_node(*page);
+            }
+
+            for (a, b) in rules.iter()
+            {
+                if u.contains(a) && u.contains(b)
                 {
-                    return Ordering::Greater;
+                    graph.add_edge(*a, *b, ());
                 }
-                return Ordering::Equal;
-            });
-            u
+            }
+
+            graph.topological_sort().unwrap()
         })
         .collect_vec();
 
@@ -1,6 +1,7 @@
 
 use itertools::*;
 use crate::support::*;
+use rayon::prelude::*;
 
 const EXAMPLE: &str = "125 17";
 
@@ -11,7 +12,7 @@ fn num_stones_after_blinks(input: &str, blinks: usize) -> usize
         .map(|s| s.parse::<usize>().unwrap())
         .collect_vec();
 
-    let memorize = Memorized::new(
+    let memorize = SyncMemorized::new(
         &|(stone, after_splits), func|
         {
             if *after_splits == 0
@@ -41,7 +42,7 @@ fn num_stones_after_blinks(input: &str, blinks: usize) -> usize
             func.get(&(2024 * stone, after_splits - 1))
         });
 
-    stones.into_iter()
+    stones.into_par_iter()
         .map(|s| memorize.get(&(s, blinks)))
         .sum()
 }
@@ -1,6 +1,5 @@
 
 use crate::support::*;
-use std::collections::HashSet;
 
 const EXAMPLE: &str = include_str!("example.txt");
 
@@ -8,27 +7,11 @@ fn fencing_price<F>(input: &str, linear_measurement: F) -> usize
     where F: Fn(&GridArea) -> usize
 {
     let grid = CharGrid::new_from_input(input, '.');
-    let mut remaining_points = grid.all_points().into_iter().collect::<HashSet<Point>>();
 
-    let mut result = 0;
-
-    while let Some(next_point) = remaining_points.iter().next()
-    {
-        let grid_area = grid.find_flood_fill_points(next_point);
-
-        remaining_points = remaining_points.into_iter()
-            .filter(|p| !grid_area.points.contains(p))
-            .collect();
-
-        let area = grid_area.area();
-        let linear = linear_measurement(&grid_area);
-
-        //println!("{} => {} x {}", field_char, area, linear);
-
-        result += area * linear;
-    }
-
-    result
+    grid.find_regions(Connectivity::Four)
+        .regions().iter()
+        .map(|region| region.grid_area.area() * linear_measurement(&region.grid_area))
+        .sum()
 }
 
 fn part_1(input: &str) -> usize
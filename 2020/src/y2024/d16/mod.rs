@@ -14,32 +14,24 @@ fn solve_maze(input: &str) -> (HashSet<Point>, i64)
         .filter(|p| grid.get_char(p) == 'E')
         .next().unwrap();
 
-    let (paths, cost) = pathfinding::directed::astar::astar_bag_collect(
-        &(start, Point::new(1, 0)),
-        |(cur, dir)|
+    let paths = dijkstra_directional(
+        GridState::new(start, Point::new(1, 0)),
+        grid.walkable_except("#"),
+        |_state, grid_move|
         {
-            let mut successors = Vec::new();
-            let forward = *cur + *dir;
-            if grid.get_char(&forward) != '#'
+            match grid_move
             {
-                successors.push(((forward, *dir), 1));
+                GridMove::Forward => Some(1),
+                GridMove::TurnLeft | GridMove::TurnRight => Some(1000),
             }
-            successors.push(((*cur, dir.rotate_90_left()), 1000));
-            successors.push(((*cur, dir.rotate_90_right()), 1000));
-            successors
         },
-        |(cur, _dir)|
-        {
-            (*cur - end).manhatten_size()
-        },
-        |(cur, _dir)| *cur == end).unwrap();
+        |state| state.pos == end).unwrap();
     
-    let points = paths.into_iter()
-        .flatten()
-        .map(|(cur, _dir)| cur)
+    let points = paths.states_on_paths().into_iter()
+        .map(|state| state.pos)
         .collect();
 
-    (points, cost)
+    (points, paths.cost() as i64)
 }
 
 fn part_1(input: &str) -> i64
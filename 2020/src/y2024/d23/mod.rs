@@ -1,5 +1,4 @@
 
-use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 use crate::support::*;
 
@@ -33,47 +32,11 @@ impl LanParty
         LanParty { computers, links }
     }
 
-    fn cliques(&self) -> Vec<HashSet<String>>
+    fn clique_graph(&self) -> CliqueGraph<String>
     {
-        let result = bron_kerbosch_maximal_cliques(
+        CliqueGraph::new(
             self.computers.iter(),
-            |v| self.links.get(v).unwrap().iter().cloned());
-
-        result
-    }
-
-    fn triples(&self) -> HashSet<Vec<String>>
-    {
-        let mut result = HashSet::new();
-        for c0 in self.computers.iter()
-        {
-            for pairs in self.links.get(c0).unwrap().iter()
-                .combinations(2)
-            {
-                if self.links.get(pairs[0]).unwrap().contains(pairs[1])
-                {
-                    let mut triple = vec![
-                        c0.clone(),
-                        pairs[0].clone(),
-                        pairs[1].clone()
-                    ];
-                    triple.sort();
-                    result.insert(triple);
-                }
-            }
-        }
-        result
-    }
-
-    fn largest_set(&self) -> HashSet<String>
-    {
-        let sets = self.cliques();
-        let largest_size = sets.iter()
-            .map(|s| s.len())
-            .max().unwrap();
-        sets.into_iter()
-            .filter(|s| s.len() == largest_size)
-            .next().unwrap()
+            |v| self.links.get(v).unwrap().iter().cloned())
     }
 }
 
@@ -81,18 +44,15 @@ fn part_1(input: &str) -> usize
 {
     let party = LanParty::new(input);
 
-    party.triples().iter()
-        .filter(|trip|
-        {
-            trip.iter().any(|c| c.starts_with("t"))
-        })
-        .count()
+    party.clique_graph()
+        .k_cliques_filtered(3, |trip| trip.iter().any(|c| c.starts_with('t')))
+        .len()
 }
 
 fn part_2(input: &str) -> String
 {
     let party = LanParty::new(input);
-    let mut largest_set = party.largest_set().into_iter().collect_vec();
+    let mut largest_set = party.clique_graph().maximum_clique();
     largest_set.sort();
     largest_set.join(",")
 }